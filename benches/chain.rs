@@ -0,0 +1,32 @@
+//! Benchmarks for error chains.
+//!
+//! Run with: `cargo bench --features bench-utils`
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use okerr::bench::{make_chain, make_wide};
+use okerr::{FormatOptions, format_chain};
+use std::hint::black_box;
+
+fn build(c: &mut Criterion) {
+    c.bench_function("make_chain(10)", |b| b.iter(|| make_chain(black_box(10))));
+    c.bench_function("make_wide(10)", |b| b.iter(|| make_wide(black_box(10))));
+}
+
+fn format(c: &mut Criterion) {
+    let err = make_chain(10);
+    let opts = FormatOptions::default();
+    c.bench_function("format_chain(10)", |b| {
+        b.iter(|| format_chain(black_box(&err), &opts))
+    });
+    c.bench_function("alternate display(10)", |b| {
+        b.iter(|| format!("{:#}", black_box(&err)))
+    });
+
+    let wide = make_wide(10);
+    c.bench_function("display make_wide(10)", |b| {
+        b.iter(|| black_box(&wide).to_string())
+    });
+}
+
+criterion_group!(benches, build, format);
+criterion_main!(benches);
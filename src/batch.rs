@@ -0,0 +1,94 @@
+//! Batch processing with configurable failure semantics.
+
+use crate::{Error, Result};
+
+/// Failures of [`process_batch`], with the index of each failing item.
+///
+/// Displayed like [`MultiError`](crate::MultiError), each error prefixed with `item {index}`.
+#[derive(Debug)]
+pub struct BatchError {
+    failures: Vec<(usize, Error)>,
+}
+
+impl BatchError {
+    /// Indices of the failing items, in ascending order.
+    pub fn indices(&self) -> Vec<usize> {
+        self.failures.iter().map(|(index, _)| *index).collect()
+    }
+
+    /// Failing items: index and error, in ascending order of index.
+    pub fn failures(&self) -> &[(usize, Error)] {
+        &self.failures
+    }
+
+    /// Take the failures.
+    pub fn into_failures(self) -> Vec<(usize, Error)> {
+        self.failures
+    }
+}
+
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::write_error_list(
+            f,
+            self.failures
+                .iter()
+                .map(|(index, err)| format!("item {index}: {err:#}")),
+        )
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// Process every item with `f`, collecting the outputs in order.
+///
+/// With `stop_on_error`, returns at the first failure (the following items are not processed).
+/// Otherwise, every item is processed and every failure is reported.
+///
+/// # Example:
+/// ```
+/// use okerr::{batch::process_batch, ensure};
+///
+/// let check = |n: &i32| {
+///     ensure!(*n >= 0, "{n} is negative");
+///     Ok(*n * 2)
+/// };
+///
+/// assert_eq!(process_batch(&[1, 2], check, false).unwrap(), [2, 4]);
+///
+/// let err = process_batch(&[1, -2, 3, -4], check, false).unwrap_err();
+/// assert_eq!(err.indices(), [1, 3]);
+///
+/// let err = process_batch(&[1, -2, 3, -4], check, true).unwrap_err();
+/// assert_eq!(err.indices(), [1]);
+/// ```
+pub fn process_batch<T, U, F>(
+    items: &[T],
+    f: F,
+    stop_on_error: bool,
+) -> std::result::Result<Vec<U>, BatchError>
+where
+    F: Fn(&T) -> Result<U>,
+{
+    let mut outputs = Vec::with_capacity(items.len());
+    let mut failures = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        match f(item) {
+            Ok(output) => outputs.push(output),
+            Err(err) => {
+                failures.push((index, err));
+
+                if stop_on_error {
+                    break;
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(outputs)
+    } else {
+        Err(BatchError { failures })
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers building representative errors, for benchmarks.
+//!
+//! Requires the `bench-utils` feature.
+
+use crate::{Error, MultiError, anyerr, anyerr_chain};
+
+/// Build an error whose chain has `depth` levels (at least 1).
+///
+/// The root cause is `level 0`, the top-level message is `level {depth - 1}`.
+pub fn make_chain(depth: usize) -> Error {
+    anyerr_chain((0..depth.max(1)).map(|level| format!("level {level}")))
+}
+
+/// Build an aggregate of `count` errors (`error 0`, `error 1`, ...).
+pub fn make_wide(count: usize) -> MultiError {
+    (0..count).map(|i| anyerr!("error {i}")).collect()
+}
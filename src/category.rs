@@ -0,0 +1,81 @@
+//! Broad categories of errors, detected from the chain.
+
+use std::io::ErrorKind;
+
+/// Broad category of an error, see [`category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// A file or resource doesn't exist.
+    NotFound,
+    /// Not allowed to access a resource.
+    PermissionDenied,
+    /// A resource already exists.
+    AlreadyExists,
+    /// Invalid input or data.
+    InvalidInput,
+    /// An operation timed out.
+    Timeout,
+    /// A network connection failed.
+    Network,
+    /// The operation has been interrupted.
+    Interrupted,
+}
+
+/// Detect the category of the error,
+/// from the first I/O error of the chain or an [`InterruptError`](crate::signal::InterruptError).
+///
+/// Returns `None` if the error can't be categorized.
+pub fn category(err: &crate::Error) -> Option<Category> {
+    if crate::signal::was_interrupted(err) {
+        return Some(Category::Interrupted);
+    }
+
+    let category = match crate::io_kind(err)? {
+        ErrorKind::NotFound => Category::NotFound,
+        ErrorKind::PermissionDenied => Category::PermissionDenied,
+        ErrorKind::AlreadyExists => Category::AlreadyExists,
+        ErrorKind::InvalidInput | ErrorKind::InvalidData => Category::InvalidInput,
+        ErrorKind::TimedOut => Category::Timeout,
+        ErrorKind::ConnectionRefused
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionAborted
+        | ErrorKind::NotConnected
+        | ErrorKind::AddrNotAvailable
+        | ErrorKind::BrokenPipe => Category::Network,
+        ErrorKind::Interrupted => Category::Interrupted,
+        _ => return None,
+    };
+
+    Some(category)
+}
+
+/// A canned suggestion to fix the error, based on its [`category`].
+///
+/// Returns `None` if the error can't be categorized.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, category::auto_hint};
+/// use std::io;
+///
+/// let result: Result<()> = Err(io::Error::from(io::ErrorKind::PermissionDenied))
+///     .context("writing /etc/hosts");
+///
+/// assert_eq!(
+///     auto_hint(&result.unwrap_err()).as_deref(),
+///     Some("try running with elevated privileges")
+/// );
+/// ```
+pub fn auto_hint(err: &crate::Error) -> Option<String> {
+    let hint = match category(err)? {
+        Category::NotFound => "check that the path or resource exists",
+        Category::PermissionDenied => "try running with elevated privileges",
+        Category::AlreadyExists => "remove the existing resource or choose another name",
+        Category::InvalidInput => "check the input values",
+        Category::Timeout => "retry later or increase the timeout",
+        Category::Network => "check your network connection",
+        Category::Interrupted => "the operation was cancelled, run it again to resume",
+    };
+
+    Some(hint.to_string())
+}
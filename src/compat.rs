@@ -0,0 +1,36 @@
+//! Conversions between okerr `Result` and boxed-error results.
+//!
+//! Useful to interop with libraries requiring `Box<dyn std::error::Error>`.
+
+use crate::Result;
+
+/// Boxed error, as used by many libraries.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Convert an okerr `Result` into a boxed-error `Result`.
+///
+/// The message and the chain (sources) are preserved.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, anyerr, compat::to_std};
+///
+/// let result: Result<()> = Err(anyerr!("inner")).context("outer");
+/// let boxed = to_std(result).unwrap_err();
+///
+/// assert_eq!(boxed.to_string(), "outer");
+/// assert_eq!(boxed.source().unwrap().to_string(), "inner");
+/// ```
+pub fn to_std<T>(result: Result<T>) -> std::result::Result<T, BoxError> {
+    result.map_err(BoxError::from)
+}
+
+/// Convert a `Result` with a boxed error (or any std error) into an okerr `Result`.
+///
+/// Same as `result.map_err(|e| okerr::from_boxed_error(e.into()))`.
+pub fn from_std<T, E>(result: std::result::Result<T, E>) -> Result<T>
+where
+    E: Into<BoxError>,
+{
+    result.map_err(|err| crate::from_boxed_error(err.into()))
+}
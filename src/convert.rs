@@ -0,0 +1,137 @@
+//! Conversions of okerr `Result` values and iterators.
+
+use crate::{Context, Result};
+
+/// Extension methods for iterators of okerr `Result`.
+///
+/// Implemented for every iterator of okerr `Result`.
+pub trait ResultIterExt<T> {
+    /// Collect the `Ok` values, short-circuiting on the first error.
+    ///
+    /// The error gets the context `stopped after {count} successes`
+    /// (the number of `Ok` values before the failure).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, convert::ResultIterExt};
+    ///
+    /// let numbers: Result<Vec<i32>> = ["1", "2", "x", "4"]
+    ///     .iter()
+    ///     .map(|s| Ok(s.parse::<i32>()?))
+    ///     .collect_first_err();
+    ///
+    /// let err = numbers.unwrap_err();
+    /// assert_eq!(err.to_string(), "stopped after 2 successes");
+    /// ```
+    fn collect_first_err(self) -> Result<Vec<T>>;
+}
+
+impl<T, I> ResultIterExt<T> for I
+where
+    I: Iterator<Item = Result<T>>,
+{
+    fn collect_first_err(self) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+
+        for result in self {
+            match result {
+                Ok(value) => values.push(value),
+                Err(err) => {
+                    let count = values.len();
+                    let noun = if count == 1 { "success" } else { "successes" };
+
+                    return Err(err).context(format!("stopped after {count} {noun}"));
+                }
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Max number of characters of the input shown by [`from_parse`].
+const PARSE_SNIPPET_LEN: usize = 40;
+
+/// Convert a parse result, with the offending input as context:
+/// `failed to parse {snippet:?}`.
+///
+/// The snippet is the first 40 characters of the input,
+/// followed by `...` if the input is longer.
+///
+/// # Example:
+/// ```
+/// use okerr::convert::from_parse;
+///
+/// let input = "80a";
+/// let err = from_parse(input.parse::<u16>(), input).unwrap_err();
+///
+/// assert_eq!(
+///     format!("{err:#}"),
+///     "failed to parse \"80a\": invalid digit found in string"
+/// );
+/// ```
+pub fn from_parse<T, E>(result: std::result::Result<T, E>, input: &str) -> Result<T>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    result.with_context(|| {
+        let mut snippet: String = input.chars().take(PARSE_SNIPPET_LEN).collect();
+
+        if snippet.len() < input.len() {
+            snippet.push_str("...");
+        }
+
+        format!("failed to parse {snippet:?}")
+    })
+}
+
+/// Build a domain type from a fallible input (e.g. a parsed or validated value),
+/// with consistent constructor errors.
+///
+/// Implement [`try_from_value`](TryFromResult::try_from_value),
+/// then use [`try_from_result`](TryFromResult::try_from_result):
+/// the errors of the input and of the construction get the context `constructing {type}`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, convert::TryFromResult, ensure};
+///
+/// #[derive(Debug)]
+/// struct Port(u16);
+///
+/// impl TryFromResult<u16> for Port {
+///     fn try_from_value(value: u16) -> Result<Self> {
+///         ensure!(value >= 1024, "port {value} is reserved");
+///         Ok(Port(value))
+///     }
+/// }
+///
+/// let port = Port::try_from_result("80".parse::<u16>().map_err(Into::into));
+/// assert_eq!(
+///     format!("{:#}", port.unwrap_err()),
+///     "constructing Port: port 80 is reserved"
+/// );
+/// ```
+pub trait TryFromResult<T>: Sized {
+    /// Build the value from the successful input.
+    fn try_from_value(value: T) -> Result<Self>;
+
+    /// Build the value from the input, failing if the input failed
+    /// or if the construction failed, with the context `constructing {type}`.
+    fn try_from_result(value: Result<T>) -> Result<Self> {
+        value
+            .and_then(Self::try_from_value)
+            .with_context(|| format!("constructing {}", short_type_name::<Self>()))
+    }
+}
+
+/// Type name without its module path (kept as is for generic types).
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+
+    if name.contains('<') {
+        return name;
+    }
+
+    name.rsplit("::").next().unwrap_or(name)
+}
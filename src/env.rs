@@ -0,0 +1,41 @@
+//! Helpers reading environment variables, with clear failures.
+
+use crate::{Context, Result, anyerr};
+use std::str::FromStr;
+
+/// Read a required environment variable.
+///
+/// Errors: `environment variable {name} is not set`
+/// or `environment variable {name} is not valid unicode`.
+///
+/// # Example:
+/// ```
+/// use okerr::env::require_var;
+///
+/// let err = require_var("OKERR_DOC_UNSET_VAR").unwrap_err();
+/// assert_eq!(err.to_string(), "environment variable OKERR_DOC_UNSET_VAR is not set");
+/// ```
+pub fn require_var(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|err| match err {
+        std::env::VarError::NotPresent => anyerr!("environment variable {name} is not set"),
+        std::env::VarError::NotUnicode(_) => {
+            anyerr!("environment variable {name} is not valid unicode")
+        }
+    })
+}
+
+/// Read and parse a required environment variable.
+///
+/// Same as [`require_var`], a parse failure gets the context
+/// `invalid value for environment variable {name}: {value:?}`.
+pub fn require_var_parsed<T>(name: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value = require_var(name)?;
+
+    value
+        .parse()
+        .with_context(|| format!("invalid value for environment variable {name}: {value:?}"))
+}
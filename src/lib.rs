@@ -8,7 +8,7 @@
 //! This crate is a re-export of `anyhow` and aliase of`thiserror`.
 //!
 //! - It provides a `Result` type that is `anyhow::Result`
-//! and `okerr::derive::Error` is an alias of `thiserror::Error`.
+//!   and `okerr::derive::Error` is an alias of `thiserror::Error`.
 //!     - NOTE: `okerr::derive::Error` requieres the `thiserror` dependency to be added to your `Cargo.toml` (`cargo add thiserror`).
 //! - It also provides a `err!` macro that is a shorthand for `Err(anyhow::anyhow!(...))` or `Err(okerr::anyerr!(...))`.
 //! - It also provides a `fail!` macro that is `anyhow::bail!`.
@@ -104,6 +104,38 @@
 //! It's very simple, very lightweight
 //! (just a few lines of code in the `okerr` crate), no overhead, no abstraction cost.
 //! `okerr` provides consistency and a excellent DX. 100% compatible with `anyhow` and `thiserror`, convert easily error from a boxed error (like eyre::Report and others).
+//!
+//! # Markers
+//!
+//! Some methods store typed markers in the error instead of a context message
+//! (e.g. [`ResultExt::mark_user`], [`ResultExt::tag`], [`ResultExt::with_data`]).
+//! The markers of an error share a single layer, found again behind later context layers.
+//!
+//! This layer takes the place of the top-level error it marks: it displays like it
+//! and continues with its source, so the message and the chain are unchanged.
+//! The marked error itself is no longer reachable by anyhow's downcasts
+//! (`err.is::<io::Error>()` is `false` on a marked I/O error);
+//! the helpers of this crate (e.g. [`ResultExt::context_on_io_kind`], [`category::category`])
+//! look through the markers layer.
+
+pub mod batch;
+#[cfg(feature = "bench-utils")]
+pub mod bench;
+pub mod category;
+pub mod compat;
+pub mod convert;
+pub mod env;
+pub mod limits;
+pub mod once;
+#[cfg(feature = "tracing")]
+pub mod panic_hook;
+pub mod redact;
+pub mod signal;
+#[cfg(feature = "future")]
+pub mod stream;
+pub mod sync;
+pub mod timing;
+pub mod validate;
 
 pub use anyhow::{
     // -- Structs
@@ -147,10 +179,72 @@ pub use anyhow::{
 /// Sugar for thiserror::Error.
 /// `okerr::derive::Error` is an alias of `thiserror::Error`.
 /// - https://docs.rs/thiserror/latest/thiserror/
+///
 /// NOTE: requieres the `thiserror` dependency.
 pub mod derive {
     // Re-export thiserror::Error
     pub use thiserror::Error;
+
+    /// Display helper for errors wrapping a single source,
+    /// consistent with thiserror's `#[error(transparent)]`.
+    ///
+    /// Implemented for every error type.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::derive::{Error, Transparent};
+    ///
+    /// #[derive(Error, Debug)]
+    /// #[error("storage error")]
+    /// struct StorageError(#[source] std::io::Error);
+    ///
+    /// let err = StorageError(std::io::Error::other("disk full"));
+    /// assert_eq!(err.transparent_display(), "disk full");
+    /// ```
+    pub trait Transparent: std::error::Error {
+        /// Display of the source, or of the error itself if it has no source.
+        fn transparent_display(&self) -> String {
+            match self.source() {
+                Some(source) => source.to_string(),
+                None => self.to_string(),
+            }
+        }
+    }
+
+    impl<E: std::error::Error + ?Sized> Transparent for E {}
+
+    /// Everything needed to define and use a custom error type, in one import.
+    ///
+    /// Brings `derive(Error)` (thiserror), the `okerr::Error` type, `Result`,
+    /// the extension traits and the common macros.
+    ///
+    /// NOTE: requieres the `thiserror` dependency.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::derive::prelude::*;
+    ///
+    /// #[derive(Error, Debug)]
+    /// enum ConfigError {
+    ///     #[error("missing key: {0}")]
+    ///     MissingKey(String),
+    /// }
+    ///
+    /// fn load() -> Result<()> {
+    ///     err!(ConfigError::MissingKey("port".into()))
+    /// }
+    ///
+    /// assert_eq!(load().unwrap_err().to_string(), "missing key: port");
+    /// ```
+    pub mod prelude {
+        // derive(Error) from thiserror (macro namespace)
+        pub use super::Error;
+
+        // okerr::Error type (type namespace)
+        pub use crate::Error;
+
+        pub use crate::{Context, OptionExt, Result, ResultExt, anyerr, ensure, err, fail, must};
+    }
 }
 
 /// Same as `anyhow!` (and its alias: `format_err!`).
@@ -174,6 +268,369 @@ macro_rules! fail {
     ($($tt:tt)*) => { $crate::bail!($($tt)*) };
 }
 
+/// Unwrap an `Option` or early return with an error (like `fail!`) if it's `None`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, must};
+///
+/// fn first_char(s: &str) -> Result<char> {
+///     let c = must!(s.chars().next(), "empty string");
+///     Ok(c)
+/// }
+///
+/// assert_eq!(first_char("abc").unwrap(), 'a');
+/// assert_eq!(first_char("").unwrap_err().to_string(), "empty string");
+/// ```
+#[macro_export]
+macro_rules! must {
+    ($opt:expr, $($tt:tt)+) => {
+        match $opt {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => $crate::fail!($($tt)+),
+        }
+    };
+}
+
+/// Ensure an `Option` is `Some`, or early return with an error (like `ensure!`).
+/// Evaluates to the inner value.
+///
+/// Same as `must!`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ensure_some};
+///
+/// fn port(value: Option<u16>) -> Result<u16> {
+///     let port = ensure_some!(value, "port must be present");
+///     Ok(port)
+/// }
+///
+/// assert_eq!(port(Some(80)).unwrap(), 80);
+/// assert_eq!(port(None).unwrap_err().to_string(), "port must be present");
+/// ```
+#[macro_export]
+macro_rules! ensure_some {
+    ($opt:expr, $($tt:tt)+) => { $crate::must!($opt, $($tt)+) };
+}
+
+/// Ensure an `Option` is `None`, or early return with an error (like `ensure!`).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ensure_none};
+///
+/// fn register(existing: Option<&str>) -> Result<()> {
+///     ensure_none!(existing, "user must be absent");
+///     Ok(())
+/// }
+///
+/// assert!(register(None).is_ok());
+/// assert_eq!(register(Some("bob")).unwrap_err().to_string(), "user must be absent");
+/// ```
+#[macro_export]
+macro_rules! ensure_none {
+    ($opt:expr, $($tt:tt)+) => {
+        if ::core::option::Option::is_some(&$opt) {
+            $crate::fail!($($tt)+);
+        }
+    };
+}
+
+/// Unwrap an `Ok` value or panic with a formatted message followed by the error chain.
+///
+/// Unlike `Result::expect`, the message supports format args
+/// and the panic prints the whole chain (`{prefix}: {err:#}`).
+///
+/// # Example:
+/// ```should_panic
+/// use okerr::{Context, Result, anyerr, expect_fmt};
+///
+/// let action = "load config";
+/// let result: Result<()> = Err(anyerr!("file not found")).context("reading config.toml");
+///
+/// // Panics with: "could not load config: reading config.toml: file not found"
+/// expect_fmt!(result, "could not {}", action);
+/// ```
+#[macro_export]
+macro_rules! expect_fmt {
+    ($result:expr, $($arg:tt)+) => {
+        match $result {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(err) => {
+                ::core::panic!("{}: {:#}", ::core::format_args!($($arg)+), err)
+            }
+        }
+    };
+}
+
+/// Build an [`ErrorSet`] from messages, duplicates are dropped.
+///
+/// # Example:
+/// ```
+/// use okerr::err_set;
+///
+/// let set = err_set!["name is empty", "age is negative", "name is empty"];
+///
+/// assert_eq!(set.len(), 2);
+/// assert_eq!(
+///     set.to_string(),
+///     "2 errors occurred:\n  - age is negative\n  - name is empty"
+/// );
+/// ```
+#[macro_export]
+macro_rules! err_set {
+    ($($msg:expr),* $(,)?) => {{
+        let mut set = $crate::ErrorSet::new();
+        $(set.insert($msg);)*
+        set
+    }};
+}
+
+/// Run a block returning a `Result`, attaching the given context to any error
+/// (e.g. propagated with `?` anywhere in the block).
+///
+/// The block runs as an immediately-invoked closure:
+/// a `return` inside the block leaves the block, not the enclosing function.
+/// The context is only evaluated on error.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, scope};
+///
+/// fn load(input: &str) -> Result<u16> {
+///     let port = scope!("loading config", {
+///         let port: u16 = input.trim().parse()?;
+///         Ok(port)
+///     })?;
+///
+///     Ok(port)
+/// }
+///
+/// assert_eq!(load("8080").unwrap(), 8080);
+/// assert_eq!(
+///     format!("{:#}", load("http").unwrap_err()),
+///     "loading config: invalid digit found in string"
+/// );
+/// ```
+#[macro_export]
+macro_rules! scope {
+    ($ctx:expr, $body:block) => {
+        $crate::Context::with_context((|| -> $crate::Result<_> { $body })(), || $ctx)
+    };
+}
+
+/// Assert that a `Result` is an error whose chain messages are exactly the expected ones
+/// (from the top-level message to the root cause).
+///
+/// On mismatch, panics with the expected and actual chains and the differing levels.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, anyerr, assert_chain};
+///
+/// let result: Result<()> = Err(anyerr!("inner")).context("outer");
+/// assert_chain!(result, ["outer", "inner"]);
+/// ```
+#[macro_export]
+macro_rules! assert_chain {
+    ($result:expr, $expected:expr $(,)?) => {
+        match $result {
+            ::core::result::Result::Ok(_) => {
+                ::core::panic!("assertion failed: expected an error chain, got Ok")
+            }
+            ::core::result::Result::Err(err) => {
+                $crate::__assert_chain(&$crate::Error::from(err), &$expected)
+            }
+        }
+    };
+}
+
+/// Evaluate every condition, and if any is true, early return a [`MultiError`]
+/// with the messages of all the true conditions (in order).
+///
+/// Useful to report all the failing preconditions at once, rather than one at a time.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, bail_all};
+///
+/// fn create_user(name: &str, age: i32) -> Result<()> {
+///     bail_all!([
+///         name.is_empty() => "name is empty",
+///         age < 0 => format!("age is negative: {age}"),
+///     ]);
+///
+///     Ok(())
+/// }
+///
+/// assert!(create_user("alice", 30).is_ok());
+/// assert_eq!(
+///     create_user("", -1).unwrap_err().to_string(),
+///     "2 errors occurred:\n  - name is empty\n  - age is negative: -1"
+/// );
+/// ```
+#[macro_export]
+macro_rules! bail_all {
+    ([$($cond:expr => $msg:expr),* $(,)?]) => {{
+        let mut errors = $crate::MultiError::new();
+        $(
+            if $cond {
+                errors.push($crate::anyhow!($msg));
+            }
+        )*
+        if !errors.is_empty() {
+            return ::core::result::Result::Err(::core::convert::From::from(errors));
+        }
+    }};
+}
+
+/// Attach the source location of the macro call (`file:line`) as context to the error.
+///
+/// The location is resolved at compile time, so it's the place where
+/// `here_context!` is written (no `#[track_caller]` involved).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, here_context};
+///
+/// let result: Result<()> = here_context!(Err(anyerr!("disk full")));
+/// let err = result.unwrap_err();
+///
+/// assert!(err.to_string().contains(".rs:"));
+/// assert_eq!(err.root_cause().to_string(), "disk full");
+/// ```
+#[macro_export]
+macro_rules! here_context {
+    ($result:expr $(,)?) => {
+        $crate::Context::context(
+            $result,
+            ::core::concat!(::core::file!(), ":", ::core::line!()),
+        )
+    };
+}
+
+/// Thread a value through a series of fallible steps: `pipe!(value => step1 => step2)`.
+///
+/// Each step is called with the output of the previous one and returns a `Result`.
+/// The first error stops the pipeline, with the context ``step `{step}` failed``
+/// (the step expression as written).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ensure, pipe};
+///
+/// fn parse(s: &str) -> Result<u16> {
+///     Ok(s.trim().parse()?)
+/// }
+///
+/// fn check_port(port: u16) -> Result<u16> {
+///     ensure!(port >= 1024, "port {port} is reserved");
+///     Ok(port)
+/// }
+///
+/// assert_eq!(pipe!(" 8080 " => parse => check_port).unwrap(), 8080);
+/// assert_eq!(
+///     format!("{:#}", pipe!("80" => parse => check_port).unwrap_err()),
+///     "step `check_port` failed: port 80 is reserved"
+/// );
+/// ```
+#[macro_export]
+macro_rules! pipe {
+    ($value:expr $(=> $step:expr)+ $(,)?) => {
+        (|| -> $crate::Result<_> {
+            let value = $value;
+            $(
+                let value = $crate::Context::context(
+                    ($step)(value),
+                    ::core::concat!("step `", ::core::stringify!($step), "` failed"),
+                )?;
+            )+
+            $crate::Ok(value)
+        })()
+    };
+}
+
+/// Check an invariant: panics in debug builds (`debug_assertions` enabled),
+/// early returns with an error in release builds (like `ensure!`).
+///
+/// Invariant violations are caught loudly during development,
+/// but degrade gracefully in production.
+/// The message is a format string with its arguments, or any `Display` expression.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ensure_invariant};
+///
+/// fn withdraw(balance: u64, amount: u64) -> Result<u64> {
+///     ensure_invariant!(amount <= balance, "overdraft: {amount} > {balance}");
+///     Ok(balance - amount)
+/// }
+///
+/// assert_eq!(withdraw(100, 30).unwrap(), 70);
+/// ```
+#[macro_export]
+macro_rules! ensure_invariant {
+    ($cond:expr, $fmt:literal $(, $($arg:tt)*)?) => {
+        if !$cond {
+            if ::core::cfg!(debug_assertions) {
+                ::core::panic!($fmt $(, $($arg)*)?);
+            }
+
+            $crate::fail!($fmt $(, $($arg)*)?);
+        }
+    };
+    ($cond:expr, $msg:expr $(,)?) => {
+        if !$cond {
+            if ::core::cfg!(debug_assertions) {
+                ::core::panic!("{}", $msg);
+            }
+
+            $crate::fail!($msg);
+        }
+    };
+}
+
+/// Attach a snapshot of the named fields of a struct (`field=value`, using `Debug`,
+/// separated by a space) as context to the error.
+///
+/// The snapshot is only built on error.
+/// Useful to capture targeted state without dumping a large struct.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, ctx_snapshot};
+///
+/// struct Job {
+///     id: u32,
+///     retries: u8,
+///     payload: Vec<u8>,
+/// }
+///
+/// impl Job {
+///     fn run(&self) -> Result<()> {
+///         ctx_snapshot!(Err(anyerr!("worker crashed")), self, [id, retries])
+///     }
+/// }
+///
+/// let job = Job { id: 7, retries: 2, payload: vec![0; 1024] };
+/// assert_eq!(
+///     format!("{:#}", job.run().unwrap_err()),
+///     "id=7 retries=2: worker crashed"
+/// );
+/// ```
+#[macro_export]
+macro_rules! ctx_snapshot {
+    ($result:expr, $obj:expr, [$($field:ident),+ $(,)?]) => {
+        $crate::Context::with_context($result, || {
+            [$(
+                ::std::format!("{}={:?}", ::core::stringify!($field), &($obj).$field)
+            ),+]
+            .join(" ")
+        })
+    };
+}
+
 /// Convert a boxed error into an okerr/anyhow Error.
 ///
 /// # Example:
@@ -206,3 +663,2882 @@ pub fn wrap_err<T, E: std::error::Error + Send + Sync + 'static>(
 ) -> Result<T> {
     result.map_err(crate::Error::new)
 }
+
+/// Options used to format an error chain.
+///
+/// See [`format_chain`] and [`write_chain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Number of spaces added per level of cause.
+    pub indent: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent: 2 }
+    }
+}
+
+/// Write the error chain into any `std::fmt::Write`, one message per line.
+///
+/// The top-level message is not indented,
+/// each cause is indented by `opts.indent` spaces per level.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, FormatOptions, anyerr, write_chain};
+///
+/// let err = Err::<(), _>(anyerr!("root")).context("outer").unwrap_err();
+///
+/// let mut out = String::new();
+/// write_chain(&mut out, &err, &FormatOptions { indent: 4 }).unwrap();
+/// assert_eq!(out, "outer\n    root");
+/// ```
+pub fn write_chain<W: std::fmt::Write>(
+    w: &mut W,
+    err: &crate::Error,
+    opts: &FormatOptions,
+) -> std::fmt::Result {
+    for (level, cause) in err.chain().enumerate() {
+        if level > 0 {
+            w.write_char('\n')?;
+        }
+
+        write!(w, "{:width$}{}", "", cause, width = level * opts.indent)?;
+    }
+
+    std::result::Result::Ok(())
+}
+
+/// Format the error chain into a `String`, one message per line.
+///
+/// Same output as [`write_chain`].
+pub fn format_chain(err: &crate::Error, opts: &FormatOptions) -> String {
+    let mut out = String::new();
+    // Writing into a String never fails
+    let _ = write_chain(&mut out, err, opts);
+    out
+}
+
+/// Extension methods for `Option`.
+pub trait OptionExt<T> {
+    /// Convert `None` into an error with the given message,
+    /// `Some(value)` into `Ok(value)`.
+    fn ok_or_fail(self, msg: impl std::fmt::Display) -> Result<T>;
+
+    /// Convert `None` into an error with the given context,
+    /// `Some(value)` into `Ok(value)`.
+    ///
+    /// Unlike [`ok_or_fail`](OptionExt::ok_or_fail), the context is only formatted
+    /// when the error is displayed. Usable in higher-order code (e.g. `map`).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{OptionExt, Result};
+    ///
+    /// fn first_word(s: &str) -> Result<&str> {
+    ///     let word = s.split_whitespace().next().ok_or_context("empty input")?;
+    ///     Ok(word)
+    /// }
+    ///
+    /// assert_eq!(first_word("hello world").unwrap(), "hello");
+    /// assert_eq!(first_word("  ").unwrap_err().to_string(), "empty input");
+    /// ```
+    fn ok_or_context<C>(self, ctx: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn ok_or_fail(self, msg: impl std::fmt::Display) -> Result<T> {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(crate::anyhow!("{msg}")),
+        }
+    }
+
+    fn ok_or_context<C>(self, ctx: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        crate::Context::context(self, ctx)
+    }
+}
+
+/// Extension methods for okerr `Result` of a `Vec`.
+pub trait VecResultExt<T> {
+    /// Convert an empty `Ok` vector into an error with the given message
+    /// (e.g. a search that returned nothing).
+    ///
+    /// Non-empty vectors and errors are returned unchanged.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, VecResultExt};
+    ///
+    /// let found: Result<Vec<&str>> = Ok(Vec::new());
+    /// assert_eq!(
+    ///     found.require_nonempty("no user matches \"bob\"").unwrap_err().to_string(),
+    ///     "no user matches \"bob\""
+    /// );
+    /// ```
+    fn require_nonempty(self, msg: impl std::fmt::Display) -> Result<Vec<T>>;
+}
+
+impl<T> VecResultExt<T> for Result<Vec<T>> {
+    fn require_nonempty(self, msg: impl std::fmt::Display) -> Result<Vec<T>> {
+        match self {
+            Result::Ok(values) if values.is_empty() => Err(crate::anyhow!("{msg}")),
+            other => other,
+        }
+    }
+}
+
+/// Extension methods for okerr `Result`.
+pub trait ResultExt<T> {
+    /// Translate the error into a typed value (e.g. an error kind enum),
+    /// the classifier is only called on `Err`.
+    ///
+    /// Useful at a boundary to convert an okerr failure into your own type,
+    /// while still being able to inspect the whole chain.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, anyerr};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Kind {
+    ///     NotFound,
+    ///     Other,
+    /// }
+    ///
+    /// impl Kind {
+    ///     fn classify(err: &okerr::Error) -> Kind {
+    ///         if err.to_string().contains("not found") {
+    ///             Kind::NotFound
+    ///         } else {
+    ///             Kind::Other
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let result: Result<()> = Err(anyerr!("user not found"));
+    /// assert_eq!(result.to_kind(Kind::classify), Err(Kind::NotFound));
+    /// ```
+    fn to_kind<K, F: FnOnce(&crate::Error) -> K>(self, f: F) -> std::result::Result<T, K>;
+
+    /// Log the `Ok` value (with its `Debug`) at info level, errors pass through untouched.
+    ///
+    /// Useful for audit trails of successful operations.
+    ///
+    /// Requires the `tracing` feature.
+    #[cfg(feature = "tracing")]
+    fn and_log_ok(self, msg: &str) -> Result<T>
+    where
+        T: std::fmt::Debug;
+
+    /// Attach the current frames of the `stack` (joined by `" > "`) as context.
+    ///
+    /// Nothing is attached if the stack is empty.
+    fn context_frames(self, stack: &ContextStack) -> Result<T>;
+
+    /// Attach a correlation ID (`correlation_id={id}`) to the chain.
+    ///
+    /// The ID can be extracted with [`correlation_id`].
+    fn correlation(self, id: impl std::fmt::Display) -> Result<T>;
+
+    /// Attach context unless the chain already reached the `guard` max depth.
+    ///
+    /// Skipped additions are counted by the guard (see [`limits::DepthGuard::suppressed`]).
+    fn context_guarded<C>(self, guard: &limits::DepthGuard, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+
+    /// Attach key/value metadata as context (`key=value` pairs, sorted by key).
+    ///
+    /// The metadata can be extracted with [`context_metadata`].
+    fn context_map(self, map: &std::collections::HashMap<String, String>) -> Result<T>;
+
+    /// Mark the error as a user error (e.g. invalid input, 4xx),
+    /// see [`is_user_error`].
+    ///
+    /// Stored as a [marker](crate#markers), the last mark set wins.
+    fn mark_user(self) -> Result<T>;
+
+    /// Mark the error as a system error (e.g. I/O failure, 5xx),
+    /// see [`is_system_error`].
+    ///
+    /// Stored as a [marker](crate#markers), the last mark set wins.
+    fn mark_system(self) -> Result<T>;
+
+    /// Attach a context computed only when the error is formatted.
+    ///
+    /// Unlike `with_context` (which runs its closure when the context is attached),
+    /// `f` runs each time the context is displayed, see [`DeferredContext`].
+    fn context_deferred<F>(self, f: F) -> Result<T>
+    where
+        F: Fn() -> String + Send + Sync + 'static;
+
+    /// Feed the `Ok` value into the fallible step `f` (like `and_then`),
+    /// attaching the context returned by `describe(&value)` if `f` fails.
+    ///
+    /// `describe` runs before `f` (which takes ownership of the value),
+    /// so it should be cheap (e.g. return an ID or a borrowed detail).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, err};
+    ///
+    /// fn save(path: String) -> Result<()> {
+    ///     err!("read-only file system")
+    /// }
+    ///
+    /// let result: Result<String> = Ok("/tmp/out.json".to_string());
+    /// let err = result
+    ///     .and_then_ctx(|path| format!("saving {path}"), save)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(format!("{err:#}"), "saving /tmp/out.json: read-only file system");
+    /// ```
+    fn and_then_ctx<U, C, D, F>(self, describe: D, f: F) -> Result<U>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        D: FnOnce(&T) -> C,
+        F: FnOnce(T) -> Result<U>;
+
+    /// Replace the error with [`signal::InterruptError`] if `flag` is set
+    /// (e.g. by a Ctrl-C handler), otherwise the error passes through.
+    ///
+    /// See [`signal::was_interrupted`].
+    fn or_interrupt(self, flag: &std::sync::atomic::AtomicBool) -> Result<T>;
+
+    /// Attach context only if an I/O error of the given `kind` is in the chain.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Context, Result, ResultExt};
+    /// use std::io;
+    ///
+    /// let result: Result<()> = Err(io::Error::from(io::ErrorKind::PermissionDenied))
+    ///     .context("writing /etc/app.conf");
+    ///
+    /// let err = result
+    ///     .context_on_io_kind(io::ErrorKind::PermissionDenied, "check the file permissions")
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.to_string(), "check the file permissions");
+    /// ```
+    fn context_on_io_kind<C>(self, kind: std::io::ErrorKind, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+
+    /// Fold a secondary error into the chain, as context of the primary error.
+    ///
+    /// An error has only one source, so the secondary error is attached
+    /// as context (its message, not its own sources).
+    /// It can still be retrieved with `downcast_ref::<E2>()`.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, anyerr};
+    /// use std::io;
+    ///
+    /// let result: Result<()> = Err(anyerr!("upload failed"));
+    /// let cleanup = io::Error::other("could not remove temp file");
+    ///
+    /// let err = result.map_err_join(cleanup).unwrap_err();
+    /// assert_eq!(format!("{err:#}"), "could not remove temp file: upload failed");
+    /// ```
+    fn map_err_join<E2>(self, secondary: E2) -> Result<T>
+    where
+        E2: std::error::Error + Send + Sync + 'static;
+
+    /// Run the fallback `f` only if `self` is an error.
+    ///
+    /// If the fallback also fails, returns a [`MultiError`]
+    /// with both errors (the primary one first).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, err};
+    ///
+    /// let primary: Result<&str> = err!("cache miss");
+    /// assert_eq!(primary.or_try(|| Ok("from database")).unwrap(), "from database");
+    /// ```
+    fn or_try<F: FnOnce() -> Result<T>>(self, f: F) -> Result<T>;
+
+    /// Attach a typed payload (e.g. the request being processed) to the error,
+    /// retrieved by type with [`get_data`].
+    ///
+    /// Stored as a [marker](crate#markers).
+    fn with_data<D: Send + Sync + 'static>(self, data: D) -> Result<T>;
+
+    /// Attach the chain of `other` as a single context line (`outer: inner: ...`),
+    /// without consuming `other`.
+    ///
+    /// Useful to record a secondary failure (e.g. a cleanup) under the main error.
+    fn context_from_err(self, other: &crate::Error) -> Result<T>;
+
+    /// Log the error (with its chain) at the given level, then return it unchanged.
+    /// `Ok` passes through silently.
+    ///
+    /// Requires the `tracing` feature.
+    #[cfg(feature = "tracing")]
+    fn log_at(self, level: tracing::Level) -> Result<T>;
+
+    /// Attach `attempt {attempt}/{max}` as context, to standardize the context of retry loops.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, anyerr};
+    ///
+    /// let result: Result<()> = Err(anyerr!("timeout"));
+    /// let err = result.context_retry(2, 5).unwrap_err();
+    ///
+    /// assert_eq!(format!("{err:#}"), "attempt 2/5: timeout");
+    /// ```
+    fn context_retry(self, attempt: usize, max: usize) -> Result<T>;
+
+    /// Attach context only in debug builds (`debug_assertions` enabled).
+    ///
+    /// In release builds, the error is returned unchanged:
+    /// verbose diagnostics during development, leaner errors in production.
+    fn context_debug_only<C>(self, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+
+    /// Add a free-form label to the error (e.g. `"db"`, `"retryable"`), see [`tags_of`].
+    ///
+    /// Stored as a [marker](crate#markers), tags accumulate across calls.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Context, Result, ResultExt, anyerr, tags_of};
+    ///
+    /// let result: Result<()> = Err(anyerr!("deadlock detected"));
+    /// let err = result
+    ///     .tag("db")
+    ///     .context("saving order")
+    ///     .tag("retryable")
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(tags_of(&err), ["db", "retryable"]);
+    /// ```
+    fn tag(self, tag: &'static str) -> Result<T>;
+
+    /// Add several labels at once, same as calling [`ResultExt::tag`] for each one.
+    fn tags(self, tags: &[&'static str]) -> Result<T>;
+
+    /// Attach the resident memory of the process (`rss={kb}KB`) as context.
+    ///
+    /// Useful to diagnose failures close to an out-of-memory.
+    /// Only supported on Linux (read from `/proc/self/statm`),
+    /// on other platforms or if unreadable, the error is returned unchanged.
+    fn context_mem(self) -> Result<T>;
+
+    /// Translate the top-level message with the localizer, see [`Localizer`].
+    ///
+    /// If the message is a key known by the localizer, the translation is added
+    /// on top of the error (the original stays in the chain as a source).
+    /// Otherwise the error is returned unchanged.
+    fn localize(self, loc: &dyn Localizer) -> Result<T>;
+
+    /// Attach the frames currently entered on this thread with [`CallerFrame::enter`]
+    /// (joined by `" > "`, as `name (file:line)`) as context.
+    ///
+    /// Nothing is attached if no frame is entered.
+    fn context_callers(self) -> Result<T>;
+
+    /// Set the process exit code to use for this error, see [`to_exit_code`].
+    ///
+    /// Stored as a [marker](crate#markers), the last code set wins.
+    /// `0` is reported as `1`, an error never exits with success.
+    fn with_exit_code(self, code: u8) -> Result<T>;
+
+    /// Attach the context computed by `f` for a random fraction `rate` (`0.0..=1.0`) of the errors.
+    ///
+    /// Useful to sample expensive diagnostics on high-volume paths.
+    /// `f` is only called when the error is sampled.
+    /// Uses a lightweight per-thread PRNG, not suitable for anything security related.
+    fn context_sampled<F>(self, rate: f64, f: F) -> Result<T>
+    where
+        F: FnOnce() -> String;
+
+    /// Convert the error into a value with `f`, consuming the `Result` into `T`.
+    ///
+    /// Useful for code that is infallible after a fallback.
+    fn map_err_into_ok<F>(self, f: F) -> T
+    where
+        F: FnOnce(crate::Error) -> T;
+
+    /// Attach context only if the `flag` is currently set.
+    ///
+    /// Useful to toggle verbose diagnostics at runtime (e.g. a feature flag in production).
+    /// The message is only formatted if it's attached.
+    fn context_flag<C>(self, flag: &std::sync::atomic::AtomicBool, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+
+    /// Remove the top-level message if it's a redundant context layer:
+    /// empty, or the same as the message of its source.
+    ///
+    /// The chain then displays from the source (see [`derive::Transparent`]),
+    /// the [markers](crate#markers) and the causes are kept.
+    /// Meaningful top messages are preserved, and the error is returned unchanged.
+    fn transparent(self) -> Result<T>;
+
+    /// Attach the originating HTTP request (`request {method} {path}`) as context.
+    ///
+    /// The whole request context can be extracted with [`request_context_of`].
+    ///
+    /// Requires the `http` feature.
+    #[cfg(feature = "http")]
+    fn request_context(self, ctx: &RequestContext) -> Result<T>;
+
+    /// Convert the error into another type, `f` takes ownership of the error.
+    ///
+    /// Like `map_err`, with the source type fixed to `okerr::Error`
+    /// (better type inference at boundaries), e.g. to downcast the error by value.
+    /// See also [`ResultExt::to_kind`] which borrows the error.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt};
+    /// use std::io;
+    ///
+    /// let result: Result<()> = Err(io::Error::other("disk full").into());
+    /// let io_err = result
+    ///     .map_err_owned(|err| err.downcast::<io::Error>())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(io_err.unwrap().to_string(), "disk full");
+    /// ```
+    fn map_err_owned<R, F>(self, f: F) -> std::result::Result<T, R>
+    where
+        F: FnOnce(crate::Error) -> R;
+
+    /// Record the outcome in the escalator, attaching context that gets more detailed
+    /// as the same operation keeps failing, see [`RetryEscalator`].
+    fn context_escalate(self, esc: &mut RetryEscalator) -> Result<T>;
+
+    /// Get the value, or `T::default()` on error, counting the fallbacks in `counter`.
+    ///
+    /// Useful for resilient code that must track how often the defaults are used.
+    fn or_default_count(self, counter: &std::sync::atomic::AtomicUsize) -> T
+    where
+        T: Default;
+
+    /// Attach the labels of the `scope` and of its enclosing scopes
+    /// (joined by `" > "`, outermost first) as context, see [`enter_context`].
+    ///
+    /// Nothing is attached if the scope was ended by dropping an enclosing scope.
+    fn in_scope(self, scope: &ContextScope) -> Result<T>;
+
+    /// Count a pipeline step, attaching `step {n}: {name}` as context on error
+    /// (`n` is the 1-based number of the step), see [`StepCounter`].
+    fn step(self, counter: &mut StepCounter, name: &str) -> Result<T>;
+
+    /// Link the error to a section of the manual, see [`doc_section`].
+    ///
+    /// Stored as a [marker](crate#markers), the last section set wins.
+    fn doc_section(self, section: &'static str) -> Result<T>;
+
+    /// Attach a context only rendered in the full debug dump of the error (`{:#?}`),
+    /// never in its `Display` (`{}` / `{:#}`) nor in its chain.
+    ///
+    /// `f` runs each time the dump is formatted, so heavy diagnostics cost nothing
+    /// in user-facing messages. The context is stored as a [marker](crate#markers).
+    ///
+    /// NOTE: anyhow's `{:?}` renders the causes with their `Display`,
+    /// only the alternate `{:#?}` delegates to the error's `Debug`.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, anyerr};
+    ///
+    /// let result: Result<()> = Err(anyerr!("query failed"));
+    /// let err = result
+    ///     .lazy_debug_context(|| "plan: seq scan on orders".to_string())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(format!("{err:#}"), "query failed");
+    /// assert!(format!("{err:#?}").contains("Debug context:\n    plan: seq scan on orders"));
+    /// ```
+    fn lazy_debug_context<F>(self, f: F) -> Result<T>
+    where
+        F: Fn() -> String + Send + Sync + 'static;
+
+    /// Attach a backoff delay suggested for retrying (e.g. HTTP `Retry-After`),
+    /// see [`retry_after`].
+    ///
+    /// Stored as a [marker](crate#markers), the last delay set wins.
+    fn retry_after(self, delay: std::time::Duration) -> Result<T>;
+
+    /// Attach context only the first time an error occurs for `key`,
+    /// the keys already seen are recorded in the shared `seen` set.
+    ///
+    /// Useful to avoid repeating identical context across many failures
+    /// (e.g. concurrent workers hitting the same outage).
+    fn context_throttled<C>(
+        self,
+        key: &str,
+        msg: C,
+        seen: &std::sync::Mutex<std::collections::HashSet<String>>,
+    ) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static;
+
+    /// Attach every environment variable whose name starts with `prefix`
+    /// as context, sorted by name (`KEY=value, KEY=value`).
+    ///
+    /// Useful to dump all the settings of an app (e.g. `MYAPP_*`) when the config fails to load.
+    /// If no variable matches, the error is returned unchanged.
+    /// Variables with a name or value that is not valid Unicode are skipped.
+    ///
+    /// # Example:
+    /// ```standalone_crate
+    /// use okerr::{Result, ResultExt, anyerr};
+    ///
+    /// // SAFETY: this doctest runs in its own process, no other thread reads the environment.
+    /// unsafe { std::env::set_var("DOCAPP_PORT", "8080") };
+    ///
+    /// let result: Result<()> = Err(anyerr!("invalid config"));
+    /// let err = result.context_env_prefixed("DOCAPP_").unwrap_err();
+    ///
+    /// assert_eq!(err.to_string(), "DOCAPP_PORT=8080");
+    /// ```
+    fn context_env_prefixed(self, prefix: &str) -> Result<T>;
+
+    /// Collapse the result into a single type, mapping the value with `ok`
+    /// or the owned error with `err`.
+    ///
+    /// Same as [`Result::map_or_else`] with the arms in the natural order,
+    /// useful at a boundary to build a unified response (e.g. an HTTP response).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, ResultExt, anyerr};
+    ///
+    /// let result: Result<u32> = Err(anyerr!("not found"));
+    /// let response = result.map_ok_or_err(|n| format!("200 {n}"), |err| format!("500 {err}"));
+    ///
+    /// assert_eq!(response, "500 not found");
+    /// ```
+    fn map_ok_or_err<R>(self, ok: impl FnOnce(T) -> R, err: impl FnOnce(crate::Error) -> R) -> R;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn to_kind<K, F: FnOnce(&crate::Error) -> K>(self, f: F) -> std::result::Result<T, K> {
+        self.map_err(|err| f(&err))
+    }
+
+    #[cfg(feature = "tracing")]
+    fn and_log_ok(self, msg: &str) -> Result<T>
+    where
+        T: std::fmt::Debug,
+    {
+        if let Result::Ok(value) = &self {
+            tracing::info!(value = ?value, "{msg}");
+        }
+
+        self
+    }
+
+    fn context_frames(self, stack: &ContextStack) -> Result<T> {
+        if stack.frames.is_empty() {
+            return self;
+        }
+
+        self.with_context(|| stack.frames.join(" > "))
+    }
+
+    fn correlation(self, id: impl std::fmt::Display) -> Result<T> {
+        self.map_err(|err| err.context(CorrelationId(id.to_string())))
+    }
+
+    fn context_guarded<C>(self, guard: &limits::DepthGuard, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        self.map_err(|err| {
+            if guard.allows(&err) {
+                err.context(msg)
+            } else {
+                err
+            }
+        })
+    }
+
+    fn context_map(self, map: &std::collections::HashMap<String, String>) -> Result<T> {
+        self.map_err(|err| {
+            let metadata = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            err.context(ContextMetadata(metadata))
+        })
+    }
+
+    fn mark_user(self) -> Result<T> {
+        self.map_err(|err| annotate(err, FaultClass::User))
+    }
+
+    fn mark_system(self) -> Result<T> {
+        self.map_err(|err| annotate(err, FaultClass::System))
+    }
+
+    fn context_deferred<F>(self, f: F) -> Result<T>
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.map_err(|err| err.context(DeferredContext(Box::new(f))))
+    }
+
+    fn and_then_ctx<U, C, D, F>(self, describe: D, f: F) -> Result<U>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        D: FnOnce(&T) -> C,
+        F: FnOnce(T) -> Result<U>,
+    {
+        let value = self?;
+        let ctx = describe(&value);
+
+        f(value).context(ctx)
+    }
+
+    fn or_interrupt(self, flag: &std::sync::atomic::AtomicBool) -> Result<T> {
+        self.map_err(|err| {
+            if flag.load(std::sync::atomic::Ordering::SeqCst) {
+                signal::InterruptError.into()
+            } else {
+                err
+            }
+        })
+    }
+
+    fn context_on_io_kind<C>(self, kind: std::io::ErrorKind, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        self.map_err(|err| {
+            if io_kind(&err) == Some(kind) {
+                err.context(msg)
+            } else {
+                err
+            }
+        })
+    }
+
+    fn map_err_join<E2>(self, secondary: E2) -> Result<T>
+    where
+        E2: std::error::Error + Send + Sync + 'static,
+    {
+        self.map_err(|err| err.context(secondary))
+    }
+
+    fn or_try<F: FnOnce() -> Result<T>>(self, f: F) -> Result<T> {
+        let primary = match self {
+            Result::Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+
+        f().map_err(|fallback| MultiError::from(vec![primary, fallback]).into())
+    }
+
+    fn with_data<D: Send + Sync + 'static>(self, data: D) -> Result<T> {
+        self.map_err(|err| annotate(err, DataCarrier(data)))
+    }
+
+    fn context_from_err(self, other: &crate::Error) -> Result<T> {
+        self.with_context(|| format!("{other:#}"))
+    }
+
+    #[cfg(feature = "tracing")]
+    fn log_at(self, level: tracing::Level) -> Result<T> {
+        use tracing::Level;
+
+        if let Err(err) = &self {
+            // The level of a tracing event must be a constant
+            if level == Level::ERROR {
+                tracing::error!("{err:#}");
+            } else if level == Level::WARN {
+                tracing::warn!("{err:#}");
+            } else if level == Level::INFO {
+                tracing::info!("{err:#}");
+            } else if level == Level::DEBUG {
+                tracing::debug!("{err:#}");
+            } else {
+                tracing::trace!("{err:#}");
+            }
+        }
+
+        self
+    }
+
+    fn context_retry(self, attempt: usize, max: usize) -> Result<T> {
+        self.with_context(|| format!("attempt {attempt}/{max}"))
+    }
+
+    fn context_debug_only<C>(self, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        if cfg!(debug_assertions) {
+            self.context(msg)
+        } else {
+            self
+        }
+    }
+
+    fn tag(self, tag: &'static str) -> Result<T> {
+        self.tags(&[tag])
+    }
+
+    fn tags(self, tags: &[&'static str]) -> Result<T> {
+        self.map_err(|err| annotate(err, Tags(tags.to_vec())))
+    }
+
+    fn context_mem(self) -> Result<T> {
+        self.map_err(|err| match resident_memory_kb() {
+            Some(kb) => err.context(format!("rss={kb}KB")),
+            None => err,
+        })
+    }
+
+    fn localize(self, loc: &dyn Localizer) -> Result<T> {
+        self.map_err(|err| {
+            let args: Vec<&dyn std::fmt::Display> = err
+                .chain()
+                .skip(1)
+                .map(|cause| cause as &dyn std::fmt::Display)
+                .collect();
+
+            match loc.translate(&err.to_string(), &args) {
+                Some(translated) => err.context(translated),
+                None => err,
+            }
+        })
+    }
+
+    fn context_callers(self) -> Result<T> {
+        self.map_err(|err| {
+            let frames: Vec<String> = CALLER_STACK.with_borrow(|frames| {
+                frames
+                    .iter()
+                    .map(|(name, location)| format!("{name} ({location})"))
+                    .collect()
+            });
+
+            if frames.is_empty() {
+                return err;
+            }
+
+            err.context(frames.join(" > "))
+        })
+    }
+
+    fn with_exit_code(self, code: u8) -> Result<T> {
+        self.map_err(|err| annotate(err, CodedError(code)))
+    }
+
+    fn context_sampled<F>(self, rate: f64, f: F) -> Result<T>
+    where
+        F: FnOnce() -> String,
+    {
+        self.map_err(|err| if sampled(rate) { err.context(f()) } else { err })
+    }
+
+    fn map_err_into_ok<F>(self, f: F) -> T
+    where
+        F: FnOnce(crate::Error) -> T,
+    {
+        unwrap_either(self.map_err(f))
+    }
+
+    fn context_flag<C>(self, flag: &std::sync::atomic::AtomicBool, msg: C) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        if flag.load(std::sync::atomic::Ordering::Relaxed) {
+            self.context(msg)
+        } else {
+            self
+        }
+    }
+
+    fn transparent(self) -> Result<T> {
+        use crate::derive::Transparent;
+
+        self.map_err(|err| {
+            let Some(top) = causes(&err).next() else {
+                return err;
+            };
+            let top_msg = top.to_string();
+            let redundant = top_msg.trim().is_empty() || top_msg == top.transparent_display();
+
+            if top.source().is_none() || !redundant {
+                return err;
+            }
+
+            crate::Error::new(Annotated {
+                error: err,
+                markers: Vec::new(),
+                transparent: true,
+            })
+        })
+    }
+
+    #[cfg(feature = "http")]
+    fn request_context(self, ctx: &RequestContext) -> Result<T> {
+        self.map_err(|err| err.context(ctx.clone()))
+    }
+
+    fn map_err_owned<R, F>(self, f: F) -> std::result::Result<T, R>
+    where
+        F: FnOnce(crate::Error) -> R,
+    {
+        self.map_err(f)
+    }
+
+    fn context_escalate(self, esc: &mut RetryEscalator) -> Result<T> {
+        match self {
+            Result::Ok(value) => {
+                esc.reset();
+                Ok(value)
+            }
+            Err(err) => {
+                esc.failures += 1;
+
+                match esc.failures {
+                    n if n >= esc.threshold => Err(err.context(format!(
+                        "persistent failure ({n} times in a row), consider alerting"
+                    ))),
+                    1 => Err(err),
+                    n => Err(err.context(format!("failed {n} times in a row"))),
+                }
+            }
+        }
+    }
+
+    fn or_default_count(self, counter: &std::sync::atomic::AtomicUsize) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(|_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            T::default()
+        })
+    }
+
+    fn in_scope(self, scope: &ContextScope) -> Result<T> {
+        self.map_err(|err| {
+            let labels = SCOPE_STACK
+                .with_borrow(|labels| labels[..scope.depth.min(labels.len())].join(" > "));
+
+            if labels.is_empty() {
+                err
+            } else {
+                err.context(labels)
+            }
+        })
+    }
+
+    fn step(self, counter: &mut StepCounter, name: &str) -> Result<T> {
+        counter.steps += 1;
+        let n = counter.steps;
+
+        self.with_context(|| format!("step {n}: {name}"))
+    }
+
+    fn doc_section(self, section: &'static str) -> Result<T> {
+        self.map_err(|err| annotate(err, DocSection(section)))
+    }
+
+    fn lazy_debug_context<F>(self, f: F) -> Result<T>
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.map_err(|err| annotate(err, LazyDebugContext(Box::new(f))))
+    }
+
+    fn retry_after(self, delay: std::time::Duration) -> Result<T> {
+        self.map_err(|err| annotate(err, RetryAfter(delay)))
+    }
+
+    fn context_throttled<C>(
+        self,
+        key: &str,
+        msg: C,
+        seen: &std::sync::Mutex<std::collections::HashSet<String>>,
+    ) -> Result<T>
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+    {
+        self.map_err(|err| {
+            let first = seen
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .insert(key.to_string());
+
+            if first { err.context(msg) } else { err }
+        })
+    }
+
+    fn context_env_prefixed(self, prefix: &str) -> Result<T> {
+        self.map_err(|err| {
+            let mut vars: Vec<(String, String)> = std::env::vars_os()
+                .filter_map(|(key, value)| {
+                    Some((key.into_string().ok()?, value.into_string().ok()?))
+                })
+                .filter(|(key, _)| key.starts_with(prefix))
+                .collect();
+
+            if vars.is_empty() {
+                return err;
+            }
+
+            vars.sort();
+
+            let pairs = vars
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            err.context(pairs)
+        })
+    }
+
+    fn map_ok_or_err<R>(self, ok: impl FnOnce(T) -> R, err: impl FnOnce(crate::Error) -> R) -> R {
+        match self {
+            Result::Ok(value) => ok(value),
+            Err(error) => err(error),
+        }
+    }
+}
+
+/// Resident memory of the process in KB, if supported by the platform.
+#[cfg(target_os = "linux")]
+fn resident_memory_kb() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    // Second field: resident set size, in pages
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+
+    Some(pages * page_size / 1024)
+}
+
+/// Resident memory of the process in KB, if supported by the platform.
+#[cfg(not(target_os = "linux"))]
+fn resident_memory_kb() -> Option<u64> {
+    None
+}
+
+thread_local! {
+    /// State of the xorshift PRNG used by [`sampled`], randomly seeded per thread.
+    static SAMPLING_STATE: std::cell::Cell<u64> = std::cell::Cell::new({
+        use std::hash::{BuildHasher, Hasher};
+
+        // Never 0, the xorshift fixed point
+        std::collections::hash_map::RandomState::new().build_hasher().finish() | 1
+    });
+}
+
+/// Whether to sample, with a probability of `rate`.
+fn sampled(rate: f64) -> bool {
+    if rate >= 1.0 {
+        return true;
+    }
+
+    if rate.is_nan() || rate <= 0.0 {
+        return false;
+    }
+
+    let random = SAMPLING_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    });
+
+    // The 53 high bits give a uniform float in [0, 1)
+    ((random >> 11) as f64 / (1u64 << 53) as f64) < rate
+}
+
+/// Kind of the first I/O error found in the chain.
+fn io_kind(err: &crate::Error) -> Option<std::io::ErrorKind> {
+    causes(err)
+        .find_map(|cause| cause.downcast_ref::<std::io::Error>())
+        .map(std::io::Error::kind)
+}
+
+/// A manually maintained stack of named frames,
+/// attached as context with [`ResultExt::context_frames`].
+///
+/// Useful to build an explicit logical call stack in async tasks,
+/// where `#[track_caller]` doesn't help.
+///
+/// # Example:
+/// ```
+/// use okerr::{ContextStack, Result, ResultExt, anyerr};
+///
+/// let mut stack = ContextStack::new();
+/// stack.push("sync_users");
+/// stack.push("fetch_page");
+///
+/// let result: Result<()> = Err(anyerr!("timeout"));
+/// let err = result.context_frames(&stack).unwrap_err();
+/// assert_eq!(err.to_string(), "sync_users > fetch_page");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContextStack {
+    frames: Vec<String>,
+}
+
+impl ContextStack {
+    /// Create an empty stack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a named frame.
+    pub fn push(&mut self, name: impl Into<String>) {
+        self.frames.push(name.into());
+    }
+
+    /// Pop the last frame.
+    pub fn pop(&mut self) -> Option<String> {
+        self.frames.pop()
+    }
+
+    /// Current frames, from the outermost to the innermost.
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+}
+
+/// Steps counter of a sequential pipeline, used by [`ResultExt::step`].
+///
+/// Numbers the context of each step without manual counting.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, StepCounter, anyerr};
+///
+/// fn deploy() -> Result<()> {
+///     let mut steps = StepCounter::new();
+///
+///     Ok::<_, okerr::Error>(()).step(&mut steps, "build")?;
+///     Ok::<_, okerr::Error>(()).step(&mut steps, "test")?;
+///     Err(anyerr!("permission denied")).step(&mut steps, "upload")?;
+///     Ok(())
+/// }
+///
+/// assert_eq!(
+///     format!("{:#}", deploy().unwrap_err()),
+///     "step 3: upload: permission denied"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StepCounter {
+    steps: usize,
+}
+
+impl StepCounter {
+    /// Create a counter, the first step is numbered 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of steps counted so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+/// Consecutive failures counter of a retried operation,
+/// used by [`ResultExt::context_escalate`].
+///
+/// The context depends on the number of consecutive failures:
+/// - first failure (below the threshold): nothing is attached.
+/// - below the threshold: `failed {n} times in a row`.
+/// - from the threshold: `persistent failure ({n} times in a row), consider alerting`.
+///
+/// A success resets the counter.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, RetryEscalator, anyerr};
+///
+/// let mut esc = RetryEscalator::new(3);
+/// let fetch = || -> Result<()> { Err(anyerr!("timeout")) };
+///
+/// assert_eq!(fetch().context_escalate(&mut esc).unwrap_err().to_string(), "timeout");
+/// assert_eq!(
+///     fetch().context_escalate(&mut esc).unwrap_err().to_string(),
+///     "failed 2 times in a row"
+/// );
+/// assert_eq!(
+///     fetch().context_escalate(&mut esc).unwrap_err().to_string(),
+///     "persistent failure (3 times in a row), consider alerting"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryEscalator {
+    threshold: usize,
+    failures: usize,
+}
+
+impl RetryEscalator {
+    /// Create an escalator, escalating from `threshold` consecutive failures.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            failures: 0,
+        }
+    }
+
+    /// Number of consecutive failures recorded.
+    pub fn failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Reset the consecutive failures counter.
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+impl Default for RetryEscalator {
+    /// Escalate from 3 consecutive failures.
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+thread_local! {
+    /// Frames entered with [`CallerFrame::enter`] on the current thread.
+    static CALLER_STACK: std::cell::RefCell<
+        Vec<(&'static str, &'static std::panic::Location<'static>)>,
+    > = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// A named frame of the thread's logical call stack, popped when dropped.
+///
+/// The stack is attached as context with [`ResultExt::context_callers`].
+/// Like [`ContextStack`], but maintained automatically in nested (non-async) calls.
+/// Dropping a frame also pops the frames entered after it, even if they are still alive.
+///
+/// # Example:
+/// ```
+/// use okerr::{CallerFrame, Result, ResultExt, anyerr};
+///
+/// fn fetch_page() -> Result<()> {
+///     let _frame = CallerFrame::enter("fetch_page");
+///     Err(anyerr!("timeout")).context_callers()
+/// }
+///
+/// fn sync_users() -> Result<()> {
+///     let _frame = CallerFrame::enter("sync_users");
+///     fetch_page()
+/// }
+///
+/// let err = sync_users().unwrap_err();
+/// assert!(err.to_string().starts_with("sync_users ("));
+/// assert!(err.to_string().contains(" > fetch_page ("));
+/// ```
+#[derive(Debug)]
+#[must_use = "the frame is popped when the guard is dropped"]
+pub struct CallerFrame {
+    // Number of frames in the stack, up to this frame
+    depth: usize,
+    // Bound to the thread of the stack
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl CallerFrame {
+    /// Push a frame named `name`, recorded with the location of the caller.
+    #[track_caller]
+    pub fn enter(name: &'static str) -> Self {
+        let location = std::panic::Location::caller();
+        let depth = CALLER_STACK.with_borrow_mut(|frames| {
+            frames.push((name, location));
+            frames.len()
+        });
+
+        Self {
+            depth,
+            _not_send: std::marker::PhantomData,
+        }
+    }
+}
+
+impl Drop for CallerFrame {
+    fn drop(&mut self) {
+        CALLER_STACK.with_borrow_mut(|frames| frames.truncate(self.depth - 1));
+    }
+}
+
+thread_local! {
+    /// Labels of the scopes entered with [`enter_context`] on the current thread.
+    static SCOPE_STACK: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// A labelled scope, removed from the thread's scope stack when dropped.
+///
+/// Created with [`enter_context`], attached with [`ResultExt::in_scope`].
+/// Dropping a scope also ends the scopes nested in it, even if they are still alive.
+#[derive(Debug)]
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct ContextScope {
+    // Number of labels in the stack, up to this scope
+    depth: usize,
+    // Bound to the thread of the stack
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for ContextScope {
+    fn drop(&mut self) {
+        SCOPE_STACK.with_borrow_mut(|labels| labels.truncate(self.depth - 1));
+    }
+}
+
+/// Enter a labelled scope (RAII), nested in the current scopes of the thread.
+///
+/// Errors tagged with [`ResultExt::in_scope`] get the full stack of labels
+/// up to this scope (`outer > inner`) as context.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, enter_context};
+///
+/// let _import = enter_context("importing users");
+/// let row = enter_context("row 42");
+///
+/// let result: Result<()> = Err(anyerr!("invalid email"));
+/// let err = result.in_scope(&row).unwrap_err();
+///
+/// assert_eq!(format!("{err:#}"), "importing users > row 42: invalid email");
+/// ```
+pub fn enter_context(label: impl Into<String>) -> ContextScope {
+    let depth = SCOPE_STACK.with_borrow_mut(|labels| {
+        labels.push(label.into());
+        labels.len()
+    });
+
+    ContextScope {
+        depth,
+        _not_send: std::marker::PhantomData,
+    }
+}
+
+/// Convert an okerr/anyhow Error into a `miette::Report`.
+///
+/// The chain is preserved: each cause becomes a source of the report.
+///
+/// Requires the `miette` feature.
+#[cfg(feature = "miette")]
+pub fn to_miette(err: &crate::Error) -> miette::Report {
+    let diagnostic = err
+        .chain()
+        .rev()
+        .fold(None, |source, cause| {
+            Some(MietteCause {
+                message: cause.to_string(),
+                source: source.map(Box::new),
+            })
+        })
+        .expect("an error chain is never empty");
+
+    miette::Report::new(diagnostic)
+}
+
+/// One level of an error chain converted by [`to_miette`].
+#[cfg(feature = "miette")]
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+struct MietteCause {
+    message: String,
+    #[source]
+    source: Option<Box<MietteCause>>,
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for MietteCause {}
+
+/// Correlation ID attached by [`ResultExt::correlation`].
+///
+/// Displayed as `correlation_id={id}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(pub String);
+
+impl std::fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "correlation_id={}", self.0)
+    }
+}
+
+/// Extract the correlation ID attached by [`ResultExt::correlation`].
+///
+/// Returns the outermost one, if any.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, ResultExt, anyerr, correlation_id};
+///
+/// let result: Result<()> = Err(anyerr!("timeout"));
+/// let err = result.correlation("req-42").context("fetching user").unwrap_err();
+///
+/// assert_eq!(correlation_id(&err).as_deref(), Some("req-42"));
+/// ```
+pub fn correlation_id(err: &crate::Error) -> Option<String> {
+    err.downcast_ref::<CorrelationId>().map(|id| id.0.clone())
+}
+
+/// Build a chained error from messages.
+///
+/// The first message is the root cause,
+/// each subsequent message wraps the previous ones as context.
+/// So the last message is the top-level message (the one displayed by `to_string()`).
+///
+/// An empty iterator produces an error with an empty message.
+///
+/// # Example:
+/// ```
+/// use okerr::anyerr_chain;
+///
+/// let err = anyerr_chain(["root cause".to_string(), "outer".to_string()]);
+///
+/// assert_eq!(err.to_string(), "outer");
+/// assert_eq!(err.root_cause().to_string(), "root cause");
+/// ```
+pub fn anyerr_chain(messages: impl IntoIterator<Item = String>) -> crate::Error {
+    let mut messages = messages.into_iter();
+    let root = crate::Error::msg(messages.next().unwrap_or_default());
+
+    messages.fold(root, |err, msg| err.context(msg))
+}
+
+/// Format the error chain as a two-column table: level index and message.
+///
+/// The index column is right-aligned to the widest level number,
+/// columns are separated by `" | "`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, format_chain_table};
+///
+/// let err = anyerr!("root").context("outer");
+/// assert_eq!(format_chain_table(&err), "0 | outer\n1 | root");
+/// ```
+pub fn format_chain_table(err: &crate::Error) -> String {
+    let last_level = err.chain().count().saturating_sub(1);
+    let width = last_level.to_string().len();
+
+    err.chain()
+        .enumerate()
+        .map(|(level, cause)| format!("{level:>width$} | {cause}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format the error chain as a tree, each cause nested under the previous level
+/// with a `└─ ` connector.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, format_chain_tree};
+///
+/// let err = anyerr!("root").context("middle").context("outer");
+/// assert_eq!(format_chain_tree(&err), "outer\n└─ middle\n   └─ root");
+/// ```
+pub fn format_chain_tree(err: &crate::Error) -> String {
+    err.chain()
+        .enumerate()
+        .map(|(level, cause)| match level {
+            0 => cause.to_string(),
+            _ => format!("{}└─ {cause}", "   ".repeat(level - 1)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format the error like anyhow's `{:?}` (message, then a numbered `Caused by:` list),
+/// each cause indented by `indent` spaces.
+///
+/// Lines of multi-line messages are aligned after the cause number.
+/// No backtrace is rendered.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, format_debug_indented};
+///
+/// let err = anyerr!("root").context("middle").context("outer");
+/// assert_eq!(
+///     format_debug_indented(&err, 2),
+///     "outer\n\nCaused by:\n  0: middle\n  1: root"
+/// );
+/// ```
+pub fn format_debug_indented(err: &crate::Error, indent: usize) -> String {
+    let mut out = err.to_string();
+    let causes: Vec<String> = err.chain().skip(1).map(|cause| cause.to_string()).collect();
+
+    if causes.is_empty() {
+        return out;
+    }
+
+    out.push_str("\n\nCaused by:");
+
+    for (number, cause) in causes.iter().enumerate() {
+        let prefix = format!("{:indent$}{number}: ", "");
+        let continuation = " ".repeat(prefix.len());
+
+        for (i, line) in cause.lines().enumerate() {
+            out.push('\n');
+            out.push_str(if i == 0 { &prefix } else { &continuation });
+            out.push_str(line);
+        }
+    }
+
+    out
+}
+
+/// Format the error chain as newline-delimited JSON (JSON Lines),
+/// one `{"level": i, "message": "..."}` object per level.
+///
+/// Useful to stream errors into log aggregators parsing JSONL.
+///
+/// Requires the `serde_json` feature.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, to_json_lines};
+///
+/// let err = anyerr!("disk full").context("saving");
+/// assert_eq!(
+///     to_json_lines(&err),
+///     "{\"level\":0,\"message\":\"saving\"}\n{\"level\":1,\"message\":\"disk full\"}"
+/// );
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn to_json_lines(err: &crate::Error) -> String {
+    err.chain()
+        .enumerate()
+        .map(|(level, cause)| {
+            serde_json::json!({ "level": level, "message": cause.to_string() }).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Multiple errors aggregated into one error.
+///
+/// Displayed as a list, each error with its chain on one line:
+///
+/// ```text
+/// 2 errors occurred:
+///   - first error: its cause
+///   - second error
+/// ```
+#[derive(Debug, Default)]
+pub struct MultiError {
+    errors: Vec<crate::Error>,
+}
+
+impl MultiError {
+    /// Create an empty aggregate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error.
+    pub fn push(&mut self, err: crate::Error) {
+        self.errors.push(err);
+    }
+
+    /// Aggregated errors, in insertion order.
+    pub fn errors(&self) -> &[crate::Error] {
+        &self.errors
+    }
+
+    /// Consume the aggregate, returning the errors.
+    pub fn into_errors(self) -> Vec<crate::Error> {
+        self.errors
+    }
+
+    /// Number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Whether there is no error.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl std::fmt::Display for MultiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_error_list(f, self.errors.iter().map(|err| format!("{err:#}")))
+    }
+}
+
+/// Write `"{n} errors occurred:"` followed by one `"  - {item}"` line per item.
+pub(crate) fn write_error_list(
+    f: &mut std::fmt::Formatter<'_>,
+    items: impl ExactSizeIterator<Item = impl std::fmt::Display>,
+) -> std::fmt::Result {
+    let noun = if items.len() == 1 { "error" } else { "errors" };
+    write!(f, "{} {noun} occurred:", items.len())?;
+
+    for item in items {
+        write!(f, "\n  - {item}")?;
+    }
+
+    std::result::Result::Ok(())
+}
+
+impl std::error::Error for MultiError {}
+
+impl From<Vec<crate::Error>> for MultiError {
+    fn from(errors: Vec<crate::Error>) -> Self {
+        Self { errors }
+    }
+}
+
+impl FromIterator<crate::Error> for MultiError {
+    fn from_iter<I: IntoIterator<Item = crate::Error>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Try each alternative in order, returning the first `Ok`.
+///
+/// If every alternative fails, returns a [`MultiError`] listing every failure.
+/// Useful for fallback providers (e.g. config sources).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, coalesce, err};
+///
+/// let config: Result<&str> = coalesce(vec![
+///     Box::new(|| err!("no env var")),
+///     Box::new(|| Ok("from file")),
+///     Box::new(|| Ok("from defaults")),
+/// ]);
+///
+/// assert_eq!(config.unwrap(), "from file");
+/// ```
+pub fn coalesce<'a, T>(alternatives: Vec<Box<dyn FnOnce() -> Result<T> + 'a>>) -> Result<T> {
+    first_ok(alternatives)
+}
+
+/// Run each attempt in order, returning the first `Ok` (the following attempts are not run).
+///
+/// If every attempt fails, returns a [`MultiError`] listing every failure.
+/// Same as [`coalesce`], for any iterator of attempts (e.g. built lazily).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, first_ok};
+///
+/// let mirrors = ["mirror-a", "mirror-b", "mirror-c"];
+/// let attempts = mirrors.into_iter().map(|mirror| {
+///     Box::new(move || {
+///         if mirror == "mirror-a" {
+///             return Err(anyerr!("{mirror} is down"));
+///         }
+///         Ok(mirror)
+///     }) as Box<dyn FnOnce() -> Result<&'static str>>
+/// });
+///
+/// assert_eq!(first_ok(attempts).unwrap(), "mirror-b");
+/// ```
+pub fn first_ok<'a, T, I>(attempts: I) -> Result<T>
+where
+    I: IntoIterator<Item = Box<dyn FnOnce() -> Result<T> + 'a>>,
+{
+    let mut errors = MultiError::new();
+
+    for attempt in attempts {
+        match attempt() {
+            Result::Ok(value) => return Ok(value),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    Err(errors.into())
+}
+
+/// Poll the futures concurrently, resolving to the first `Ok`.
+///
+/// The remaining futures are dropped as soon as one succeeds.
+/// If every future fails (or there is none), resolves to a [`MultiError`]
+/// listing every failure in the order of `futures`.
+/// Executor agnostic: the futures are polled by the returned future.
+///
+/// Requires the `future` feature.
+///
+/// # Example:
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, FutureExt};
+/// use okerr::{anyerr, race};
+///
+/// let mirror = race(vec![
+///     future::ready(Err(anyerr!("mirror 1 down"))).boxed(),
+///     future::ready(Ok("mirror 2")).boxed(),
+/// ]);
+///
+/// assert_eq!(block_on(mirror).unwrap(), "mirror 2");
+/// ```
+#[cfg(feature = "future")]
+pub fn race<'a, T: 'a>(
+    futures: Vec<futures_core::future::BoxFuture<'a, Result<T>>>,
+) -> impl std::future::Future<Output = Result<T>> + 'a {
+    let mut errors: Vec<Option<crate::Error>> = futures.iter().map(|_| None).collect();
+    let mut pending: Vec<_> = futures.into_iter().map(Some).collect();
+
+    std::future::poll_fn(move |cx| {
+        for (slot, error) in pending.iter_mut().zip(errors.iter_mut()) {
+            let Some(future) = slot else {
+                continue;
+            };
+
+            if let std::task::Poll::Ready(result) = future.as_mut().poll(cx) {
+                *slot = None;
+
+                match result {
+                    Result::Ok(value) => return std::task::Poll::Ready(Ok(value)),
+                    Err(err) => *error = Some(err),
+                }
+            }
+        }
+
+        if pending.iter().any(Option::is_some) {
+            return std::task::Poll::Pending;
+        }
+
+        let errors: MultiError = errors.drain(..).flatten().collect();
+        std::task::Poll::Ready(Err(errors.into()))
+    })
+}
+
+/// Combine two independent results into a tuple.
+///
+/// If only one fails, its error is returned unchanged.
+/// If both fail, returns a [`MultiError`] listing both failures (`a` first).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, zip};
+///
+/// let host: Result<&str> = Ok("localhost");
+/// let port: Result<u16> = Ok(8080);
+/// assert_eq!(zip(host, port).unwrap(), ("localhost", 8080));
+///
+/// let host: Result<&str> = Err(anyerr!("missing host"));
+/// let port: Result<u16> = Err(anyerr!("invalid port"));
+/// assert_eq!(
+///     zip(host, port).unwrap_err().to_string(),
+///     "2 errors occurred:\n  - missing host\n  - invalid port"
+/// );
+/// ```
+pub fn zip<A, B>(a: Result<A>, b: Result<B>) -> Result<(A, B)> {
+    match (a, b) {
+        (Result::Ok(a), Result::Ok(b)) => Ok((a, b)),
+        (Err(err), Result::Ok(_)) | (Result::Ok(_), Err(err)) => Err(err),
+        (Err(a), Err(b)) => Err(MultiError::from(vec![a, b]).into()),
+    }
+}
+
+/// Combine three independent results into a tuple, like [`zip`].
+///
+/// If only one fails, its error is returned unchanged.
+/// If several fail, returns a [`MultiError`] listing every failure (in argument order).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, zip3};
+///
+/// let host: Result<&str> = Err(anyerr!("missing host"));
+/// let port: Result<u16> = Ok(8080);
+/// let user: Result<&str> = Err(anyerr!("missing user"));
+/// assert_eq!(
+///     zip3(host, port, user).unwrap_err().to_string(),
+///     "2 errors occurred:\n  - missing host\n  - missing user"
+/// );
+/// ```
+pub fn zip3<A, B, C>(a: Result<A>, b: Result<B>, c: Result<C>) -> Result<(A, B, C)> {
+    match (a, b, c) {
+        (Result::Ok(a), Result::Ok(b), Result::Ok(c)) => Ok((a, b, c)),
+        (a, b, c) => {
+            let mut errors: Vec<crate::Error> =
+                [a.err(), b.err(), c.err()].into_iter().flatten().collect();
+
+            if errors.len() == 1 {
+                Err(errors.remove(0))
+            } else {
+                Err(MultiError::from(errors).into())
+            }
+        }
+    }
+}
+
+/// Get the inner value of a `Result` whose both variants hold the same type.
+///
+/// See also [`ResultExt::map_err_into_ok`].
+///
+/// # Example:
+/// ```
+/// use okerr::unwrap_either;
+///
+/// let cached: Result<&str, &str> = Err("stale");
+/// assert_eq!(unwrap_either(cached), "stale");
+/// ```
+pub fn unwrap_either<T>(result: std::result::Result<T, T>) -> T {
+    match result {
+        Result::Ok(value) | Err(value) => value,
+    }
+}
+
+/// Apply the fallible operation `f` to each item, stopping at the first error.
+///
+/// The error gets the context `failed on item #{index}` (0-based).
+/// Useful for validation loops that don't produce values.
+///
+/// # Example:
+/// ```
+/// use okerr::{ensure, try_each};
+///
+/// let check = |n: &i32| {
+///     ensure!(*n >= 0, "{n} is negative");
+///     Ok(())
+/// };
+///
+/// assert!(try_each(&[1, 2, 3], |n| check(n)).is_ok());
+///
+/// let err = try_each(&[1, -2, 3], |n| check(n)).unwrap_err();
+/// assert_eq!(format!("{err:#}"), "failed on item #1: -2 is negative");
+/// ```
+pub fn try_each<I, T, F>(items: I, mut f: F) -> Result<()>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> Result<()>,
+{
+    for (index, item) in items.into_iter().enumerate() {
+        f(&item).with_context(|| format!("failed on item #{index}"))?;
+    }
+
+    Ok(())
+}
+
+/// Run `f`, converting a panic into an error `panicked: {msg}`,
+/// otherwise returning the result of `f`.
+///
+/// The panic hook still runs (e.g. the message is printed to stderr by default).
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, catch_result};
+///
+/// let result: Result<u8> = catch_result(|| panic!("index out of bounds"));
+/// assert_eq!(result.unwrap_err().to_string(), "panicked: index out of bounds");
+///
+/// assert_eq!(catch_result(|| Ok(1)).unwrap(), 1);
+/// ```
+pub fn catch_result<T, F: FnOnce() -> Result<T>>(f: F) -> Result<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+
+        Err(crate::anyhow!("panicked: {message}"))
+    })
+}
+
+/// Turn a `Vec` of results into a result of `Vec`, in order.
+///
+/// The first error gets the context `element {index} of {len} failed` (0-based index).
+/// See also [`TryCollect`] for iterators.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, sequence};
+///
+/// assert_eq!(sequence(vec![Ok(1), Ok(2)]).unwrap(), [1, 2]);
+///
+/// let results: Vec<Result<u8>> = vec![Ok(1), Err(anyerr!("bad row")), Ok(3)];
+/// assert_eq!(
+///     format!("{:#}", sequence(results).unwrap_err()),
+///     "element 1 of 3 failed: bad row"
+/// );
+/// ```
+pub fn sequence<T>(results: Vec<Result<T>>) -> Result<Vec<T>> {
+    let len = results.len();
+    let mut values = Vec::with_capacity(len);
+
+    for (index, result) in results.into_iter().enumerate() {
+        values.push(result.with_context(|| format!("element {index} of {len} failed"))?);
+    }
+
+    Ok(values)
+}
+
+/// Collect fallible key/value entries into a `HashMap`.
+///
+/// The first failing entry gets the context `entry #{index} failed` (0-based),
+/// a duplicated key fails with `entry #{index} duplicates the key of entry #{first}`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, collect_map};
+///
+/// let parse = |line: &str| -> Result<(String, u16)> {
+///     let (name, port) = line.split_once('=').unwrap_or((line, ""));
+///     Ok((name.to_string(), port.parse()?))
+/// };
+///
+/// let ports = collect_map(["http=80", "https=443"].map(parse)).unwrap();
+/// assert_eq!(ports["https"], 443);
+///
+/// let err = collect_map(["http=80", "http=8080"].map(parse)).unwrap_err();
+/// assert_eq!(err.to_string(), "entry #1 duplicates the key of entry #0");
+/// ```
+pub fn collect_map<K, V, I>(iter: I) -> Result<std::collections::HashMap<K, V>>
+where
+    K: Eq + std::hash::Hash,
+    I: IntoIterator<Item = Result<(K, V)>>,
+{
+    let mut entries: std::collections::HashMap<K, (usize, V)> = std::collections::HashMap::new();
+
+    for (index, entry) in iter.into_iter().enumerate() {
+        let (key, value) = entry.with_context(|| format!("entry #{index} failed"))?;
+
+        if let Some((first, _)) = entries.get(&key) {
+            crate::bail!("entry #{index} duplicates the key of entry #{first}");
+        }
+
+        entries.insert(key, (index, value));
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(key, (_, value))| (key, value))
+        .collect())
+}
+
+/// Collect the `Ok` values, or fold every error into a single chained error.
+///
+/// The first error is kept as the root cause (its type can still be downcast),
+/// each following error is added on top as a context layer (with its chain on one line).
+/// Unlike [`MultiError`], the result is a plain chain.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, anyerr, fold_errors};
+///
+/// let all_ok: Vec<Result<u8>> = vec![Ok(1), Ok(2)];
+/// assert_eq!(fold_errors(all_ok).unwrap(), [1, 2]);
+///
+/// let results = vec![Ok(1), Err(anyerr!("row 2 invalid")), Err(anyerr!("row 3 invalid"))];
+/// assert_eq!(
+///     format!("{:#}", fold_errors(results).unwrap_err()),
+///     "row 3 invalid: row 2 invalid"
+/// );
+/// ```
+pub fn fold_errors<I, T>(iter: I) -> Result<Vec<T>>
+where
+    I: IntoIterator<Item = Result<T>>,
+{
+    let mut values = Vec::new();
+    let mut folded: Option<crate::Error> = None;
+
+    for result in iter {
+        match (result, folded.take()) {
+            (Result::Ok(value), previous) => {
+                values.push(value);
+                folded = previous;
+            }
+            (Err(err), None) => folded = Some(err),
+            (Err(err), Some(previous)) => folded = Some(previous.context(format!("{err:#}"))),
+        }
+    }
+
+    match folded {
+        Some(err) => Err(err),
+        None => Ok(values),
+    }
+}
+
+/// Key/value metadata attached by [`ResultExt::context_map`].
+///
+/// Displayed as `key=value` pairs separated by a space, sorted by key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMetadata(pub std::collections::BTreeMap<String, String>);
+
+impl std::fmt::Display for ContextMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            write!(f, "{key}={value}")?;
+        }
+
+        std::result::Result::Ok(())
+    }
+}
+
+/// Extract the metadata attached by [`ResultExt::context_map`].
+///
+/// Returns the outermost metadata, or an empty map if there is none.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, context_metadata};
+/// use std::collections::HashMap;
+///
+/// let map = HashMap::from([("user".to_string(), "42".to_string())]);
+/// let result: Result<()> = Err(anyerr!("denied"));
+/// let err = result.context_map(&map).unwrap_err();
+///
+/// assert_eq!(err.to_string(), "user=42");
+/// assert_eq!(context_metadata(&err), map);
+/// ```
+pub fn context_metadata(err: &crate::Error) -> std::collections::HashMap<String, String> {
+    err.downcast_ref::<ContextMetadata>()
+        .map(|metadata| metadata.0.clone().into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// Layer holding the typed markers of an error, see [markers](crate#markers).
+///
+/// Takes the place of the top-level error it marks:
+/// displays like it and continues with its source.
+/// A `transparent` layer takes the place of the source instead, see [`ResultExt::transparent`].
+struct Annotated {
+    error: crate::Error,
+    markers: Vec<Box<dyn std::any::Any + Send + Sync>>,
+    transparent: bool,
+}
+
+impl Annotated {
+    /// The error this layer takes the place of.
+    fn marked(&self) -> &(dyn std::error::Error + 'static) {
+        match self.error.source() {
+            Some(source) if self.transparent => source,
+            _ => self.error.as_ref(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Annotated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.error, f)?;
+
+        let mut contexts = self
+            .markers
+            .iter()
+            .filter_map(|marker| marker.downcast_ref::<LazyDebugContext>())
+            .peekable();
+
+        if contexts.peek().is_some() {
+            f.write_str("\n\nDebug context:")?;
+
+            for context in contexts {
+                write!(f, "\n    {}", (context.0)())?;
+            }
+        }
+
+        std::result::Result::Ok(())
+    }
+}
+
+impl std::fmt::Display for Annotated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.marked())
+    }
+}
+
+impl std::error::Error for Annotated {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.marked().source()
+    }
+}
+
+/// Context attached by [`ResultExt::lazy_debug_context`], only rendered by `Debug`.
+struct LazyDebugContext(Box<dyn Fn() -> String + Send + Sync>);
+
+/// Store a marker in the markers layer of the error (also behind context layers),
+/// adding one on top if there is none.
+fn annotate<M: Send + Sync + 'static>(mut err: crate::Error, marker: M) -> crate::Error {
+    if let Some(annotated) = err.downcast_mut::<Annotated>() {
+        annotated.markers.push(Box::new(marker));
+        return err;
+    }
+
+    crate::Error::new(Annotated {
+        error: err,
+        markers: vec![Box::new(marker)],
+        transparent: false,
+    })
+}
+
+/// The chain of the error, with each markers layer replaced by the error it marks
+/// (same message and source), so the marked error can still be downcast.
+pub(crate) fn causes(
+    err: &crate::Error,
+) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+    err.chain().map(|mut cause| {
+        while let Some(annotated) = cause.downcast_ref::<Annotated>() {
+            cause = annotated.marked();
+        }
+
+        cause
+    })
+}
+
+/// Get the last marker of type `M` stored with [`annotate`].
+fn annotation<M: 'static>(err: &crate::Error) -> Option<&M> {
+    annotations::<M>(err).last()
+}
+
+/// Get all the markers of type `M` stored with [`annotate`], in insertion order.
+fn annotations<M: 'static>(err: &crate::Error) -> impl Iterator<Item = &M> {
+    let mut layers = Vec::new();
+    let mut next = err.downcast_ref::<Annotated>();
+
+    while let Some(annotated) = next {
+        layers.push(annotated);
+        next = annotated.error.downcast_ref::<Annotated>();
+    }
+
+    layers
+        .into_iter()
+        .rev()
+        .flat_map(|annotated| annotated.markers.iter())
+        .filter_map(|marker| marker.downcast_ref::<M>())
+}
+
+/// Fault class of an error, see [`ResultExt::mark_user`] and [`ResultExt::mark_system`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultClass {
+    /// Client mistake (e.g. invalid input, 4xx).
+    User,
+    /// Server fault (e.g. I/O failure, 5xx).
+    System,
+}
+
+/// Fault class of the error, unmarked errors are system errors.
+///
+/// The last mark wins.
+pub fn fault_class(err: &crate::Error) -> FaultClass {
+    annotation::<FaultClass>(err)
+        .copied()
+        .unwrap_or(FaultClass::System)
+}
+
+/// Whether the error has been marked as a user error.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, ResultExt, anyerr, is_user_error};
+///
+/// let result: Result<()> = Err(anyerr!("invalid email"));
+/// let err = result.mark_user().context("signing up").unwrap_err();
+///
+/// assert!(is_user_error(&err));
+/// assert!(!is_user_error(&anyerr!("disk full")));
+/// ```
+pub fn is_user_error(err: &crate::Error) -> bool {
+    fault_class(err) == FaultClass::User
+}
+
+/// Whether the error is a system error (marked as such, or unmarked).
+pub fn is_system_error(err: &crate::Error) -> bool {
+    fault_class(err) == FaultClass::System
+}
+
+/// Context evaluated at format time, attached by [`ResultExt::context_deferred`].
+///
+/// The closure runs each time the context is formatted (never if it's not).
+pub struct DeferredContext(pub Box<dyn Fn() -> String + Send + Sync>);
+
+impl std::fmt::Display for DeferredContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&(self.0)())
+    }
+}
+
+impl std::fmt::Debug for DeferredContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DeferredContext").field(&(self.0)()).finish()
+    }
+}
+
+/// A set of distinct error messages.
+///
+/// Like [`MultiError`], but duplicate messages are dropped
+/// and the messages are rendered sorted (deterministic output).
+/// Useful to collect validation failures that can arise from multiple sources.
+///
+/// See also the [`err_set!`] macro.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorSet {
+    messages: std::collections::BTreeSet<String>,
+}
+
+impl ErrorSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a message, returns `false` if it was already present.
+    pub fn insert(&mut self, msg: impl std::fmt::Display) -> bool {
+        self.messages.insert(msg.to_string())
+    }
+
+    /// Add an error (with its chain on one line), returns `false` if it was already present.
+    pub fn insert_error(&mut self, err: &crate::Error) -> bool {
+        self.insert(format!("{err:#}"))
+    }
+
+    /// Messages, sorted.
+    pub fn messages(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.messages.iter().map(String::as_str)
+    }
+
+    /// Number of distinct messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether there is no message.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Tolerate up to `max_allowed` errors.
+    ///
+    /// Returns `Ok` if the set has at most `max_allowed` messages, otherwise
+    /// an error wrapping the whole set.
+    ///
+    /// With the `tracing` feature, the tolerated messages are logged with `tracing::warn!`.
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::ErrorSet;
+    ///
+    /// let set: ErrorSet = ["row 3: bad date", "row 9: bad amount"].into_iter().collect();
+    ///
+    /// assert!(set.clone().into_result_threshold(2).is_ok());
+    /// assert!(set.into_result_threshold(1).is_err());
+    /// ```
+    pub fn into_result_threshold(self, max_allowed: usize) -> Result<()> {
+        if self.len() > max_allowed {
+            return Err(crate::Error::new(self));
+        }
+
+        #[cfg(feature = "tracing")]
+        for msg in self.messages() {
+            tracing::warn!("tolerated error: {msg}");
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ErrorSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_error_list(f, self.messages())
+    }
+}
+
+impl std::error::Error for ErrorSet {}
+
+impl<M: std::fmt::Display> FromIterator<M> for ErrorSet {
+    fn from_iter<I: IntoIterator<Item = M>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|msg| {
+            set.insert(msg);
+        });
+        set
+    }
+}
+
+/// Stable hash of the error chain messages, to group similar errors
+/// (e.g. in aggregation dashboards).
+///
+/// Uses FNV-1a, so the fingerprint is stable across processes and Rust versions.
+/// See also [`fingerprint_normalized`].
+pub fn fingerprint(err: &crate::Error) -> u64 {
+    fingerprint_messages(err.chain().map(|cause| cause.to_string()))
+}
+
+/// Same as [`fingerprint`], but each run of digits is replaced with `N` before hashing.
+///
+/// So messages differing only by numbers (IDs, ports, counts) share a fingerprint.
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr, fingerprint, fingerprint_normalized};
+///
+/// let a = anyerr!("user 42 not found");
+/// let b = anyerr!("user 1337 not found");
+///
+/// assert_ne!(fingerprint(&a), fingerprint(&b));
+/// assert_eq!(fingerprint_normalized(&a), fingerprint_normalized(&b));
+/// ```
+pub fn fingerprint_normalized(err: &crate::Error) -> u64 {
+    fingerprint_messages(err.chain().map(|cause| {
+        let mut normalized = String::new();
+        let mut in_digits = false;
+
+        for c in cause.to_string().chars() {
+            if !c.is_ascii_digit() {
+                normalized.push(c);
+            } else if !in_digits {
+                normalized.push('N');
+            }
+
+            in_digits = c.is_ascii_digit();
+        }
+
+        normalized
+    }))
+}
+
+/// Bucket the errors by [`fingerprint`], the groups are in first-seen order.
+///
+/// Useful to summarize the errors of a batch run into distinct groups.
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr, group_by_fingerprint};
+///
+/// let groups = group_by_fingerprint(vec![
+///     anyerr!("timeout"),
+///     anyerr!("disk full"),
+///     anyerr!("timeout"),
+/// ]);
+///
+/// let summary: Vec<(String, usize)> = groups
+///     .iter()
+///     .map(|(_, errors)| (errors[0].to_string(), errors.len()))
+///     .collect();
+/// assert_eq!(summary, [("timeout".to_string(), 2), ("disk full".to_string(), 1)]);
+/// ```
+pub fn group_by_fingerprint(errors: Vec<crate::Error>) -> Vec<(u64, Vec<crate::Error>)> {
+    let mut groups: Vec<(u64, Vec<crate::Error>)> = Vec::new();
+    let mut positions: std::collections::HashMap<u64, usize> = std::collections::HashMap::new();
+
+    for err in errors {
+        let fingerprint = fingerprint(&err);
+
+        match positions.get(&fingerprint) {
+            Some(&position) => groups[position].1.push(err),
+            None => {
+                positions.insert(fingerprint, groups.len());
+                groups.push((fingerprint, vec![err]));
+            }
+        }
+    }
+
+    groups
+}
+
+/// FNV-1a hash of the messages, each one followed by a separator.
+fn fingerprint_messages(messages: impl Iterator<Item = String>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    messages.fold(OFFSET_BASIS, |hash, msg| {
+        // 0xff never appears in UTF-8, so it can't be confused with the messages
+        msg.bytes().chain([0xff]).fold(hash, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    })
+}
+
+/// Collect an iterator of `Result` into a container, with positional context.
+///
+/// Implemented for every iterator of okerr `Result`.
+pub trait TryCollect<T> {
+    /// Collect the `Ok` values into `C`, short-circuiting on the first error.
+    ///
+    /// Unlike collecting into a `Result`, the error gets the context
+    /// `collection failed at element {index}` (0-based).
+    ///
+    /// # Example:
+    /// ```
+    /// use okerr::{Result, TryCollect};
+    ///
+    /// let numbers: Result<Vec<i32>> = ["1", "2", "x"]
+    ///     .iter()
+    ///     .map(|s| Ok(s.parse::<i32>()?))
+    ///     .try_collect();
+    ///
+    /// let err = numbers.unwrap_err();
+    /// assert_eq!(err.to_string(), "collection failed at element 2");
+    /// ```
+    fn try_collect<C: FromIterator<T>>(self) -> Result<C>;
+}
+
+impl<T, I> TryCollect<T> for I
+where
+    I: Iterator<Item = Result<T>>,
+{
+    fn try_collect<C: FromIterator<T>>(self) -> Result<C> {
+        let mut failure = None;
+
+        let collected = self
+            .enumerate()
+            .map_while(|(index, result)| match result {
+                Result::Ok(value) => Some(value),
+                Err(err) => {
+                    failure = Some(err.context(format!("collection failed at element {index}")));
+                    None
+                }
+            })
+            .collect();
+
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(collected),
+        }
+    }
+}
+
+/// Keep the top `max` levels of the chain,
+/// the remaining causes are replaced by a single `... (N more causes)` cause.
+///
+/// The error is returned unchanged if the chain is not deeper than `max`.
+/// Otherwise the error is rebuilt from the messages (the original types are lost).
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr_chain, truncate_chain};
+///
+/// let err = anyerr_chain(["root", "middle", "outer"].map(String::from));
+/// let err = truncate_chain(err, 1);
+///
+/// assert_eq!(format!("{err:#}"), "outer: ... (2 more causes)");
+/// ```
+pub fn truncate_chain(err: crate::Error, max: usize) -> crate::Error {
+    let depth = err.chain().count();
+
+    if depth <= max {
+        return err;
+    }
+
+    let summary = match depth - max {
+        1 => "... (1 more cause)".to_string(),
+        n => format!("... ({n} more causes)"),
+    };
+    let kept: Vec<String> = err
+        .chain()
+        .take(max)
+        .map(|cause| cause.to_string())
+        .collect();
+
+    anyerr_chain(std::iter::once(summary).chain(kept.into_iter().rev()))
+}
+
+/// Collapse the whitespace of each message of the chain (newlines, tabs, repeated spaces)
+/// into single spaces, for clean single-line logs.
+///
+/// The error is rebuilt from the messages (the original types are lost).
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, normalize};
+///
+/// let err = anyerr!("expected `;`\n  at line 3\n").context("parsing  config");
+/// let err = normalize(err);
+///
+/// assert_eq!(format!("{err:#}"), "parsing config: expected `;` at line 3");
+/// ```
+pub fn normalize(err: crate::Error) -> crate::Error {
+    let messages: Vec<String> = err
+        .chain()
+        .map(|cause| {
+            cause
+                .to_string()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+
+    anyerr_chain(messages.into_iter().rev())
+}
+
+/// Short excerpt of the top-level message: its first `max_words` words followed by `...`.
+///
+/// Messages that fit are returned intact.
+/// Useful for compact notifications (e.g. chat alerts).
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr, summarize};
+///
+/// let err = anyerr!("payment provider returned an unexpected response");
+///
+/// assert_eq!(summarize(&err, 3), "payment provider returned...");
+/// assert_eq!(summarize(&err, 10), "payment provider returned an unexpected response");
+/// ```
+pub fn summarize(err: &crate::Error, max_words: usize) -> String {
+    let msg = err.to_string();
+    let words: Vec<&str> = msg.split_whitespace().collect();
+
+    if words.len() <= max_words {
+        return msg;
+    }
+
+    format!("{}...", words[..max_words].join(" "))
+}
+
+/// HTTP status attached by [`from_status`].
+///
+/// Displayed as `HTTP {code} {reason}` (e.g. `HTTP 404 Not Found`).
+///
+/// Requires the `http` feature.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpStatus(pub http::StatusCode);
+
+#[cfg(feature = "http")]
+impl std::fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {}", self.0.as_u16())?;
+
+        if let Some(reason) = self.0.canonical_reason() {
+            write!(f, " {reason}")?;
+        }
+
+        std::result::Result::Ok(())
+    }
+}
+
+#[cfg(feature = "http")]
+impl std::error::Error for HttpStatus {}
+
+/// Build an error from an HTTP response status, like `HTTP 404 Not Found`.
+///
+/// The optional response body becomes the source of the error.
+/// The status can be recovered with [`http_status`].
+///
+/// Requires the `http` feature.
+///
+/// # Example:
+/// ```
+/// use okerr::{from_status, http_status};
+/// use http::StatusCode;
+///
+/// let err = from_status(StatusCode::NOT_FOUND, Some("no such user".to_string()));
+///
+/// assert_eq!(format!("{err:#}"), "HTTP 404 Not Found: no such user");
+/// assert_eq!(http_status(&err), Some(StatusCode::NOT_FOUND));
+/// ```
+#[cfg(feature = "http")]
+pub fn from_status(status: http::StatusCode, body: Option<String>) -> crate::Error {
+    match body {
+        Some(body) => crate::Error::msg(body).context(HttpStatus(status)),
+        None => crate::Error::new(HttpStatus(status)),
+    }
+}
+
+/// Extract the HTTP status attached by [`from_status`].
+///
+/// Requires the `http` feature.
+#[cfg(feature = "http")]
+pub fn http_status(err: &crate::Error) -> Option<http::StatusCode> {
+    err.downcast_ref::<HttpStatus>().map(|status| status.0)
+}
+
+/// HTTP request attached by [`ResultExt::request_context`].
+///
+/// Displayed as `request {method} {path}`, the headers are not displayed
+/// (they may contain credentials) but can be read from the extracted struct.
+///
+/// Requires the `http` feature.
+///
+/// # Example:
+/// ```
+/// use okerr::{RequestContext, Result, ResultExt, anyerr, request_context_of};
+///
+/// let request = RequestContext {
+///     method: "GET".to_string(),
+///     path: "/users/42".to_string(),
+///     headers: vec![("x-request-id".to_string(), "abc".to_string())],
+/// };
+///
+/// let result: Result<()> = Err(anyerr!("user not found"));
+/// let err = result.request_context(&request).unwrap_err();
+///
+/// assert_eq!(format!("{err:#}"), "request GET /users/42: user not found");
+/// assert_eq!(request_context_of(&err), Some(&request));
+/// ```
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    /// HTTP method (e.g. `GET`).
+    pub method: String,
+    /// Request path (e.g. `/users/42`).
+    pub path: String,
+    /// Request headers (name, value).
+    pub headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "http")]
+impl std::fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "request {} {}", self.method, self.path)
+    }
+}
+
+/// Extract the request context attached by [`ResultExt::request_context`].
+///
+/// Requires the `http` feature.
+#[cfg(feature = "http")]
+pub fn request_context_of(err: &crate::Error) -> Option<&RequestContext> {
+    err.downcast_ref::<RequestContext>()
+}
+
+/// Problem details of an API error (RFC 7807), see [`to_problem_details`].
+///
+/// Serialized with the RFC field names (`type`, `title`, `status`, `detail`).
+///
+/// Requires the `http` and `serde` features.
+#[cfg(all(feature = "http", feature = "serde"))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type (`about:blank` by default).
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Short summary of the problem type (the reason phrase of the status).
+    pub title: String,
+    /// HTTP status code.
+    pub status: u16,
+    /// Explanation of this occurrence of the problem (the error chain on one line).
+    pub detail: String,
+}
+
+/// Build the RFC 7807 problem details of the error for the HTTP `status`.
+///
+/// `title` is the canonical reason of the status (`Unknown Status` if none),
+/// `detail` is the error chain on one line.
+///
+/// Requires the `http` and `serde` features.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, to_problem_details};
+///
+/// let err = anyerr!("user 42 not found").context("loading profile");
+/// let problem = to_problem_details(&err, 404);
+///
+/// assert_eq!(problem.title, "Not Found");
+/// assert_eq!(problem.detail, "loading profile: user 42 not found");
+/// ```
+#[cfg(all(feature = "http", feature = "serde"))]
+pub fn to_problem_details(err: &crate::Error, status: u16) -> ProblemDetails {
+    let title = http::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown Status");
+
+    ProblemDetails {
+        type_: "about:blank".to_string(),
+        title: title.to_string(),
+        status,
+        detail: format!("{err:#}"),
+    }
+}
+
+/// Whether both errors have the same chain messages, level by level.
+///
+/// Useful for precise error assertions in tests, see also [`chain_diff`].
+pub fn chains_equal(a: &crate::Error, b: &crate::Error) -> bool {
+    a.chain()
+        .map(|cause| cause.to_string())
+        .eq(b.chain().map(|cause| cause.to_string()))
+}
+
+/// Compare the chain messages level by level,
+/// returning `(level, message of a, message of b)` for each differing level.
+///
+/// When a chain is shorter, its missing levels are empty strings.
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr, chain_diff};
+///
+/// let a = anyerr!("timeout").context("fetching user");
+/// let b = anyerr!("refused").context("fetching user");
+///
+/// assert_eq!(
+///     chain_diff(&a, &b),
+///     [(1, "timeout".to_string(), "refused".to_string())]
+/// );
+/// ```
+pub fn chain_diff(a: &crate::Error, b: &crate::Error) -> Vec<(usize, String, String)> {
+    let a: Vec<String> = a.chain().map(|cause| cause.to_string()).collect();
+    let b: Vec<String> = b.chain().map(|cause| cause.to_string()).collect();
+
+    (0..a.len().max(b.len()))
+        .filter_map(|level| {
+            let left = a.get(level).cloned().unwrap_or_default();
+            let right = b.get(level).cloned().unwrap_or_default();
+
+            (left != right).then_some((level, left, right))
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_chain(err: &crate::Error, expected: &[&str]) {
+    let actual: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+
+    if actual.iter().eq(expected.iter()) {
+        return;
+    }
+
+    let mut diff = String::new();
+
+    for level in 0..actual.len().max(expected.len()) {
+        match (expected.get(level), actual.get(level)) {
+            (Some(e), Some(a)) if e == a => {}
+            (Some(e), Some(a)) => diff += &format!("\n  level {level}: expected {e:?}, got {a:?}"),
+            (Some(e), None) => diff += &format!("\n  level {level}: expected {e:?}, got nothing"),
+            (None, Some(a)) => diff += &format!("\n  level {level}: unexpected {a:?}"),
+            (None, None) => {}
+        }
+    }
+
+    panic!(
+        "assertion failed: error chain mismatch\nexpected: {expected:?}\n  actual: {actual:?}\ndiff:{diff}"
+    );
+}
+
+/// Payload attached by [`ResultExt::with_data`].
+struct DataCarrier<D>(D);
+
+/// Get the payload of type `D` attached by [`ResultExt::with_data`].
+///
+/// If several payloads of the same type are attached, returns the last one.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, ResultExt, anyerr, get_data};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Request {
+///     id: u32,
+/// }
+///
+/// let result: Result<()> = Err(anyerr!("timeout"));
+/// let err = result.with_data(Request { id: 7 }).context("handling").unwrap_err();
+///
+/// assert_eq!(get_data::<Request>(&err), Some(&Request { id: 7 }));
+/// assert_eq!(get_data::<String>(&err), None);
+/// ```
+pub fn get_data<D: 'static>(err: &crate::Error) -> Option<&D> {
+    annotation::<DataCarrier<D>>(err).map(|carrier| &carrier.0)
+}
+
+/// Labels attached by [`ResultExt::tag`] and [`ResultExt::tags`].
+struct Tags(Vec<&'static str>);
+
+/// Labels attached to the error by [`ResultExt::tag`] and [`ResultExt::tags`],
+/// in insertion order, without duplicates.
+///
+/// Useful to categorize errors for metrics without a dedicated enum.
+pub fn tags_of(err: &crate::Error) -> Vec<&'static str> {
+    let mut tags: Vec<&'static str> = Vec::new();
+
+    for tag in annotations::<Tags>(err).flat_map(|tags| tags.0.iter()) {
+        if !tags.contains(tag) {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+/// Translation of error messages, see [`ResultExt::localize`].
+///
+/// # Example:
+/// ```
+/// use okerr::{Localizer, Result, ResultExt, anyerr};
+/// use std::fmt::Display;
+///
+/// struct French;
+///
+/// impl Localizer for French {
+///     fn translate(&self, key: &str, _args: &[&dyn Display]) -> Option<String> {
+///         match key {
+///             "file not found" => Some("fichier introuvable".to_string()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let result: Result<()> = Err(anyerr!("file not found"));
+/// let err = result.localize(&French).unwrap_err();
+///
+/// assert_eq!(err.to_string(), "fichier introuvable");
+/// assert_eq!(err.root_cause().to_string(), "file not found");
+/// ```
+pub trait Localizer {
+    /// Translate the message `key`, or `None` if the key is unknown.
+    ///
+    /// With [`ResultExt::localize`], `args` are the underlying causes of the error
+    /// (from the closest to the root cause).
+    fn translate(&self, key: &str, args: &[&dyn std::fmt::Display]) -> Option<String>;
+}
+
+/// Exit code attached by [`ResultExt::with_exit_code`].
+struct CodedError(u8);
+
+/// Exit code of the process for the error:
+/// the code set with [`ResultExt::with_exit_code`], or `1` by default.
+///
+/// An error never exits with success: a code set to `0` becomes `1`.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, to_exit_code};
+/// use std::process::ExitCode;
+///
+/// let result: Result<()> = Err(anyerr!("invalid arguments"));
+/// let err = result.with_exit_code(2).unwrap_err();
+///
+/// assert_eq!(to_exit_code(&err), ExitCode::from(2));
+/// assert_eq!(to_exit_code(&anyerr!("boom")), ExitCode::FAILURE);
+/// ```
+pub fn to_exit_code(err: &crate::Error) -> std::process::ExitCode {
+    std::process::ExitCode::from(annotation::<CodedError>(err).map_or(1, |coded| coded.0.max(1)))
+}
+
+/// Run the main logic of a program, returning the exit code of the process.
+///
+/// On error, the error (with its chain) is printed to stderr
+/// and the code is mapped with [`to_exit_code`].
+///
+/// # Example:
+/// ```no_run
+/// use okerr::{Result, main_result};
+/// use std::process::ExitCode;
+///
+/// fn run() -> Result<()> {
+///     Ok(())
+/// }
+///
+/// fn main() -> ExitCode {
+///     main_result(run)
+/// }
+/// ```
+pub fn main_result<F: FnOnce() -> Result<()>>(f: F) -> std::process::ExitCode {
+    match f() {
+        Result::Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            to_exit_code(&err)
+        }
+    }
+}
+
+/// Backoff delay attached by [`ResultExt::retry_after`].
+struct RetryAfter(std::time::Duration);
+
+/// Backoff delay attached by [`ResultExt::retry_after`],
+/// so a retry loop can honor the delay suggested by a server.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, Result, ResultExt, anyerr, retry_after};
+/// use std::time::Duration;
+///
+/// let result: Result<()> = Err(anyerr!("HTTP 429 Too Many Requests"));
+/// let err = result
+///     .retry_after(Duration::from_secs(30))
+///     .context("fetching rates")
+///     .unwrap_err();
+///
+/// assert_eq!(retry_after(&err), Some(Duration::from_secs(30)));
+/// ```
+pub fn retry_after(err: &crate::Error) -> Option<std::time::Duration> {
+    annotation::<RetryAfter>(err).map(|retry| retry.0)
+}
+
+/// Manual section attached by [`ResultExt::doc_section`].
+struct DocSection(&'static str);
+
+/// Section of the manual attached by [`ResultExt::doc_section`].
+pub fn doc_section(err: &crate::Error) -> Option<&'static str> {
+    annotation::<DocSection>(err).map(|section| section.0)
+}
+
+/// Format the error chain on one line, followed by a reference to the manual
+/// if a section is attached: `See the '{section}' section of the manual.`
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, format_with_doc_section};
+///
+/// let result: Result<()> = Err(anyerr!("invalid config key `colour`"));
+/// let err = result.doc_section("Configuration").unwrap_err();
+///
+/// assert_eq!(err.to_string(), "invalid config key `colour`");
+/// assert_eq!(
+///     format_with_doc_section(&err),
+///     "invalid config key `colour`\nSee the 'Configuration' section of the manual."
+/// );
+/// ```
+pub fn format_with_doc_section(err: &crate::Error) -> String {
+    match doc_section(err) {
+        Some(section) => format!("{err:#}\nSee the '{section}' section of the manual."),
+        None => format!("{err:#}"),
+    }
+}
+
+/// Display the whole error chain on one line, separated by `": "` (Go-style wrapping).
+///
+/// Useful for single-line logs that still show the chain.
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, FlattenDisplay, anyerr};
+///
+/// let err = anyerr!("connection refused").context("fetching user");
+/// assert_eq!(
+///     format!("{}", FlattenDisplay(&err)),
+///     "fetching user: connection refused"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FlattenDisplay<'a>(pub &'a crate::Error);
+
+impl std::fmt::Display for FlattenDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (level, cause) in self.0.chain().enumerate() {
+            if level > 0 {
+                f.write_str(": ")?;
+            }
+
+            write!(f, "{cause}")?;
+        }
+
+        std::result::Result::Ok(())
+    }
+}
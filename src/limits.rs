@@ -0,0 +1,64 @@
+//! Limits protecting against runaway errors.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Caps the depth of an error chain built with [`ResultExt::context_guarded`].
+///
+/// Once the chain reaches `max_depth` levels, additional context is skipped
+/// and counted as suppressed. Useful in recursive code, to avoid an unbounded
+/// accumulation of context.
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, limits::DepthGuard};
+///
+/// let guard = DepthGuard::new(2);
+/// let result: Result<()> = Err(anyerr!("root"));
+///
+/// let err = result
+///     .context_guarded(&guard, "level 1")
+///     .context_guarded(&guard, "level 2")
+///     .unwrap_err();
+///
+/// assert_eq!(err.chain().count(), 2);
+/// assert_eq!(guard.suppressed(), 1);
+/// ```
+///
+/// [`ResultExt::context_guarded`]: crate::ResultExt::context_guarded
+#[derive(Debug)]
+pub struct DepthGuard {
+    max_depth: usize,
+    suppressed: AtomicUsize,
+}
+
+impl DepthGuard {
+    /// Create a guard allowing chains of at most `max_depth` levels.
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            suppressed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Max depth of the chain.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Number of context additions skipped so far.
+    pub fn suppressed(&self) -> usize {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Whether one more level can be added to `err`, counts it as suppressed otherwise.
+    ///
+    /// Markers (e.g. [`ResultExt::mark_user`](crate::ResultExt::mark_user)) are not levels.
+    pub(crate) fn allows(&self, err: &crate::Error) -> bool {
+        if crate::causes(err).count() < self.max_depth {
+            return true;
+        }
+
+        self.suppressed.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+}
@@ -0,0 +1,61 @@
+//! Fallible lazy initialization.
+
+use crate::Result;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// A cell initialized once by a fallible function.
+///
+/// Unlike `std::sync::OnceLock`, a failed initialization is not cached:
+/// the next call retries. A successful initialization is cached thereafter.
+///
+/// # Example:
+/// ```
+/// use okerr::{err, once::TryOnce};
+///
+/// static CONFIG: TryOnce<String> = TryOnce::new();
+///
+/// assert!(CONFIG.get_or_try_init(|| err!("not ready")).is_err());
+///
+/// let config = CONFIG.get_or_try_init(|| Ok("loaded".to_string())).unwrap();
+/// assert_eq!(config, "loaded");
+/// ```
+#[derive(Debug, Default)]
+pub struct TryOnce<T> {
+    cell: OnceLock<T>,
+    init: Mutex<()>,
+}
+
+impl<T> TryOnce<T> {
+    /// Create an uninitialized cell.
+    pub const fn new() -> Self {
+        Self {
+            cell: OnceLock::new(),
+            init: Mutex::new(()),
+        }
+    }
+
+    /// Get the value, if initialized.
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+
+    /// Get the value, initializing it with `f` if needed.
+    ///
+    /// If `f` fails, its error is returned and the cell stays uninitialized.
+    /// Concurrent callers wait for the running initialization.
+    pub fn get_or_try_init<F: FnOnce() -> Result<T>>(&self, f: F) -> Result<&T> {
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+
+        let _guard = self.init.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Initialized while waiting for the lock
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+
+        let value = f()?;
+        Ok(self.cell.get_or_init(|| value))
+    }
+}
@@ -0,0 +1,55 @@
+//! Log panics as errors with tracing.
+//!
+//! Requires the `tracing` feature.
+
+use std::panic::{self, PanicHookInfo};
+use std::sync::{Mutex, PoisonError};
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Hook replaced by [`install_panic_hook`], restored by [`uninstall_panic_hook`].
+static PREVIOUS_HOOK: Mutex<Option<PanicHook>> = Mutex::new(None);
+
+/// Set a panic hook converting panics into `tracing::error!` events.
+///
+/// The event message is `panicked: {message}`, with a `location` field (`file:line:column`).
+/// The previous hook is kept, to be restored by [`uninstall_panic_hook`].
+/// Does nothing if the hook is already installed.
+pub fn install_panic_hook() {
+    let mut previous = PREVIOUS_HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if previous.is_some() {
+        return;
+    }
+
+    *previous = Some(panic::take_hook());
+    panic::set_hook(Box::new(log_panic));
+}
+
+/// Restore the panic hook replaced by [`install_panic_hook`].
+///
+/// Does nothing if the hook is not installed.
+pub fn uninstall_panic_hook() {
+    let previous = PREVIOUS_HOOK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+
+    if let Some(hook) = previous {
+        panic::set_hook(hook);
+    }
+}
+
+fn log_panic(info: &PanicHookInfo<'_>) {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => tracing::error!(location = %location, "panicked: {message}"),
+        None => tracing::error!("panicked: {message}"),
+    }
+}
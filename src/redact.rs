@@ -0,0 +1,81 @@
+//! Scrub sensitive data from error messages before sharing error reports.
+
+use crate::anyerr_chain;
+
+/// Placeholder replacing each anonymized path.
+const PATH_PLACEHOLDER: &str = "<path>";
+
+/// Replace the absolute filesystem paths in each message of the chain with `<path>`.
+///
+/// A path starts at the beginning of a word (or after an opening quote or bracket)
+/// with `/` (Unix) or a drive letter like `C:\` or `C:/` (Windows),
+/// and ends at the first whitespace, quote, closing bracket, `,`, `;` or `:`.
+/// Relative paths and URLs are left untouched.
+///
+/// Useful to strip the usernames embedded in home directory paths.
+/// The error is rebuilt from the messages (the original types are lost).
+///
+/// # Example:
+/// ```
+/// use okerr::{Context, anyerr, redact::anonymize_paths};
+///
+/// let err = anyerr!("/home/alice/app.toml: permission denied").context("loading config");
+/// let err = anonymize_paths(err);
+///
+/// assert_eq!(format!("{err:#}"), "loading config: <path>: permission denied");
+/// ```
+pub fn anonymize_paths(err: crate::Error) -> crate::Error {
+    let messages: Vec<String> = err
+        .chain()
+        .map(|cause| anonymize_message(&cause.to_string()))
+        .collect();
+
+    anyerr_chain(messages.into_iter().rev())
+}
+
+fn anonymize_message(msg: &str) -> String {
+    let chars: Vec<char> = msg.chars().collect();
+    let mut out = String::with_capacity(msg.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let at_word_start = i == 0 || is_path_opener(chars[i - 1]);
+
+        match at_word_start
+            .then(|| path_prefix_len(&chars[i..]))
+            .flatten()
+        {
+            Some(prefix) => {
+                let rest = &chars[i + prefix..];
+                let len = rest.iter().take_while(|&&c| !is_path_end(c)).count();
+
+                out.push_str(PATH_PLACEHOLDER);
+                i += prefix + len;
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Length of the absolute path prefix (`/` or `C:\`) at the start of `chars`, if any.
+/// A lone `/` (e.g. in "and / or") is not a path.
+fn path_prefix_len(chars: &[char]) -> Option<usize> {
+    match chars {
+        ['/', next, ..] if !is_path_end(*next) => Some(1),
+        [drive, ':', '\\' | '/', ..] if drive.is_ascii_alphabetic() => Some(3),
+        _ => None,
+    }
+}
+
+fn is_path_opener(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | '[' | '<' | '=')
+}
+
+fn is_path_end(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '`' | ')' | ']' | '>' | ',' | ';' | ':')
+}
@@ -0,0 +1,27 @@
+//! Distinguish user cancellation (e.g. Ctrl-C) from real failures.
+
+use thiserror::Error;
+
+/// The operation has been interrupted by the user.
+///
+/// See [`ResultExt::or_interrupt`](crate::ResultExt::or_interrupt).
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("interrupted")]
+pub struct InterruptError;
+
+/// Whether the error is (or wraps, anywhere in its chain) an [`InterruptError`].
+///
+/// # Example:
+/// ```
+/// use okerr::{Result, ResultExt, anyerr, signal::was_interrupted};
+/// use std::sync::atomic::AtomicBool;
+///
+/// let interrupted = AtomicBool::new(true);
+/// let result: Result<()> = Err(anyerr!("broken pipe"));
+/// let err = result.or_interrupt(&interrupted).unwrap_err();
+///
+/// assert!(was_interrupted(&err));
+/// ```
+pub fn was_interrupted(err: &crate::Error) -> bool {
+    crate::causes(err).any(|cause| cause.is::<InterruptError>())
+}
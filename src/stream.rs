@@ -0,0 +1,50 @@
+//! Helpers for streams of okerr `Result`.
+//!
+//! Requires the `future` feature.
+
+use crate::Result;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+/// Forward every item of the stream unchanged, logging each `Err` item.
+///
+/// With the `tracing` feature, each error is logged with `tracing::error!`
+/// (including its chain). Without it, items are only forwarded.
+///
+/// Useful in pipelines where the errors must be observed, but not dropped.
+pub fn log_errors<S, T>(stream: S) -> impl Stream<Item = Result<T>>
+where
+    S: Stream<Item = Result<T>>,
+{
+    LogErrors { stream }
+}
+
+pin_project_lite::pin_project! {
+    struct LogErrors<S> {
+        #[pin]
+        stream: S,
+    }
+}
+
+impl<S, T> Stream for LogErrors<S>
+where
+    S: Stream<Item = Result<T>>,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.project().stream.poll_next(cx));
+
+        #[cfg(feature = "tracing")]
+        if let Some(Err(err)) = &item {
+            tracing::error!("{err:#}");
+        }
+
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
@@ -0,0 +1,37 @@
+//! Helpers for channels of okerr `Result`.
+
+use crate::{Error, Result};
+use std::sync::mpsc::Receiver;
+
+/// Consume every message of the channel (until all senders are dropped),
+/// partitioning the successes and the errors, in the order received.
+///
+/// Useful to collect the outputs of workers.
+///
+/// # Example:
+/// ```
+/// use okerr::{anyerr, sync::drain_channel};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// tx.send(Ok(1)).unwrap();
+/// tx.send(Err(anyerr!("worker failed"))).unwrap();
+/// drop(tx);
+///
+/// let (values, errors) = drain_channel(rx);
+/// assert_eq!(values, [1]);
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn drain_channel<T>(rx: Receiver<Result<T>>) -> (Vec<T>, Vec<Error>) {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in rx {
+        match result {
+            Ok(value) => values.push(value),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    (values, errors)
+}
@@ -0,0 +1,134 @@
+//! Helpers attaching timing information to errors.
+
+use crate::{Context, Result, anyerr};
+use std::time::{Duration, Instant};
+
+/// Run `f`, attaching `{label} failed after {ms}ms` as context if it fails.
+///
+/// # Example:
+/// ```
+/// use okerr::{err, timing::timed_context};
+///
+/// let result: okerr::Result<()> = timed_context("fetching users", || err!("timeout"));
+///
+/// let err = result.unwrap_err();
+/// assert!(err.to_string().starts_with("fetching users failed after "));
+/// ```
+pub fn timed_context<T, F: FnOnce() -> Result<T>>(label: &str, f: F) -> Result<T> {
+    let start = Instant::now();
+
+    f().with_context(|| format!("{label} failed after {}ms", start.elapsed().as_millis()))
+}
+
+/// Run `f`, failing with `exceeded deadline by {overrun:?}` if it completes after `deadline`.
+///
+/// A late error gets this context, a late success becomes an error.
+/// The deadline is only checked after completion: `f` is not interrupted.
+///
+/// # Example:
+/// ```
+/// use okerr::timing::with_deadline;
+/// use std::time::{Duration, Instant};
+///
+/// let deadline = Instant::now() + Duration::from_secs(60);
+/// assert_eq!(with_deadline(deadline, || Ok(42)).unwrap(), 42);
+/// ```
+pub fn with_deadline<T, F: FnOnce() -> Result<T>>(deadline: Instant, f: F) -> Result<T> {
+    let result = f();
+    let now = Instant::now();
+
+    if now <= deadline {
+        return result;
+    }
+
+    let overrun = now - deadline;
+
+    match result {
+        Ok(_) => Err(anyerr!("exceeded deadline by {overrun:?}")),
+        Err(err) => Err(err.context(format!("exceeded deadline by {overrun:?}"))),
+    }
+}
+
+/// Run `f`, attaching `{msg} (took {elapsed:?})` as context if it fails
+/// after more than `threshold`.
+///
+/// Fast errors and successes are returned unchanged.
+/// Useful to distinguish timeout-like failures from immediate ones.
+///
+/// # Example:
+/// ```
+/// use okerr::{err, timing::slow_context};
+/// use std::time::Duration;
+///
+/// let result: okerr::Result<()> =
+///     slow_context(Duration::from_secs(5), "slow upstream", || err!("refused"));
+///
+/// // Failed fast: no slow context
+/// assert_eq!(format!("{:#}", result.unwrap_err()), "refused");
+/// ```
+pub fn slow_context<T, F>(threshold: Duration, msg: impl std::fmt::Display, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    if elapsed <= threshold {
+        return result;
+    }
+
+    result.with_context(|| format!("{msg} (took {elapsed:?})"))
+}
+
+/// Run `f`, attaching the CPU time consumed by the process during the call
+/// (`cpu_time={ms}ms`) as context if it fails.
+///
+/// Useful to tell CPU-bound failures from ones waiting on I/O.
+/// Only supported on Linux (`CLOCK_PROCESS_CPUTIME_ID`),
+/// on other platforms the result is returned unchanged.
+///
+/// # Example:
+/// ```
+/// use okerr::{err, timing::context_cpu};
+///
+/// let result: okerr::Result<()> = context_cpu(|| err!("hash mismatch"));
+///
+/// let err = result.unwrap_err();
+/// # #[cfg(target_os = "linux")]
+/// assert!(err.to_string().starts_with("cpu_time="));
+/// ```
+pub fn context_cpu<T, F: FnOnce() -> Result<T>>(f: F) -> Result<T> {
+    let start = process_cpu_time();
+    let result = f();
+
+    match (start, result) {
+        (Some(start), Err(err)) => match process_cpu_time() {
+            Some(end) => Err(err.context(format!(
+                "cpu_time={}ms",
+                end.saturating_sub(start).as_millis()
+            ))),
+            None => Err(err),
+        },
+        (_, result) => result,
+    }
+}
+
+/// CPU time consumed by the process so far, if supported by the platform.
+#[cfg(target_os = "linux")]
+fn process_cpu_time() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `ts` is a valid, writable timespec.
+    let rc = unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut ts) };
+
+    (rc == 0).then(|| Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cpu_time() -> Option<Duration> {
+    None
+}
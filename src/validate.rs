@@ -0,0 +1,116 @@
+//! Validators returning descriptive errors naming the validated field.
+//!
+//! Useful for form validation, the messages are user-friendly.
+
+use crate::{Result, ensure};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Ensure the string is not empty.
+///
+/// Error: `{field} must not be empty`.
+///
+/// # Example:
+/// ```
+/// use okerr::validate::ensure_nonempty_str;
+///
+/// assert!(ensure_nonempty_str("Alice", "name").is_ok());
+/// assert_eq!(
+///     ensure_nonempty_str("", "name").unwrap_err().to_string(),
+///     "name must not be empty"
+/// );
+/// ```
+pub fn ensure_nonempty_str(s: &str, field: &str) -> Result<()> {
+    ensure!(!s.is_empty(), "{field} must not be empty");
+    Ok(())
+}
+
+/// Ensure the string length (in characters) is between `min` and `max` (inclusive).
+///
+/// Error: `{field} must be between {min} and {max} characters long (got {len})`.
+pub fn ensure_len_between(s: &str, min: usize, max: usize, field: &str) -> Result<()> {
+    let len = s.chars().count();
+
+    ensure!(
+        (min..=max).contains(&len),
+        "{field} must be between {min} and {max} characters long (got {len})"
+    );
+    Ok(())
+}
+
+/// Ensure the slice is sorted in ascending order (equal neighbours are allowed).
+///
+/// Error: `{field} must be sorted ({prev:?} comes before {next:?})`,
+/// naming the first out-of-order pair.
+///
+/// # Example:
+/// ```
+/// use okerr::validate::ensure_sorted;
+///
+/// assert!(ensure_sorted(&[1, 2, 2, 5], "ids").is_ok());
+/// assert_eq!(
+///     ensure_sorted(&[1, 5, 3], "ids").unwrap_err().to_string(),
+///     "ids must be sorted (5 comes before 3)"
+/// );
+/// ```
+pub fn ensure_sorted<T: Ord + Debug>(slice: &[T], field: &str) -> Result<()> {
+    if let Some(pair) = slice.windows(2).find(|pair| pair[0] > pair[1]) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        crate::bail!("{field} must be sorted ({prev:?} comes before {next:?})");
+    }
+
+    Ok(())
+}
+
+/// Ensure all the values of the slice are unique (hash based).
+///
+/// Error: `{field} must contain unique values ({value:?} is duplicated)`,
+/// naming the first value seen twice.
+///
+/// # Example:
+/// ```
+/// use okerr::validate::ensure_unique;
+///
+/// assert!(ensure_unique(&["a", "b"], "tags").is_ok());
+/// assert_eq!(
+///     ensure_unique(&["a", "b", "a"], "tags").unwrap_err().to_string(),
+///     "tags must contain unique values (\"a\" is duplicated)"
+/// );
+/// ```
+pub fn ensure_unique<T: Eq + Hash + Debug>(slice: &[T], field: &str) -> Result<()> {
+    let mut seen = HashSet::with_capacity(slice.len());
+
+    if let Some(value) = slice.iter().find(|value| !seen.insert(*value)) {
+        crate::bail!("{field} must contain unique values ({value:?} is duplicated)");
+    }
+
+    Ok(())
+}
+
+/// Ensure the slice has no duplicated values (ordering based).
+///
+/// Same as [`ensure_unique`] for types that implement `Ord` but not `Hash`.
+///
+/// Error: `{field} must not contain duplicates ({value:?} is duplicated)`,
+/// naming the first value seen twice.
+pub fn ensure_no_duplicates<T: Ord + Debug>(slice: &[T], field: &str) -> Result<()> {
+    let mut seen = BTreeSet::new();
+
+    if let Some(value) = slice.iter().find(|value| !seen.insert(*value)) {
+        crate::bail!("{field} must not contain duplicates ({value:?} is duplicated)");
+    }
+
+    Ok(())
+}
+
+/// Ensure the string matches the regex.
+///
+/// Error: `{field} must match the pattern {re}`.
+///
+/// Requires the `regex` feature.
+#[cfg(feature = "regex")]
+pub fn ensure_matches_regex(s: &str, re: &regex::Regex, field: &str) -> Result<()> {
+    ensure!(re.is_match(s), "{field} must match the pattern {re}");
+    Ok(())
+}
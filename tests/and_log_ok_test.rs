@@ -0,0 +1,37 @@
+//! Tests for ResultExt::and_log_ok()
+
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::Capture;
+use okerr::{Result, ResultExt, anyerr};
+use tracing::Level;
+
+#[test]
+fn and_log_ok_logs_value_on_ok() {
+    let capture = Capture::default();
+
+    let result: Result<Vec<i32>> = capture.run(|| Ok(vec![1, 2]).and_log_ok("user created"));
+
+    assert_eq!(result.unwrap(), [1, 2]);
+
+    let events = capture.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].level, Level::INFO);
+    assert_eq!(events[0].message, "user created");
+    assert_eq!(
+        events[0].fields,
+        [("value".to_string(), "[1, 2]".to_string())]
+    );
+}
+
+#[test]
+fn and_log_ok_silent_on_err() {
+    let capture = Capture::default();
+
+    let result: Result<i32> = capture.run(|| Err(anyerr!("failed")).and_log_ok("user created"));
+
+    assert_eq!(result.unwrap_err().to_string(), "failed");
+    assert!(capture.events().is_empty());
+}
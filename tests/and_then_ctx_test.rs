@@ -0,0 +1,66 @@
+//! Tests for ResultExt::and_then_ctx()
+
+use okerr::{Result, ResultExt, anyerr, err};
+use std::cell::Cell;
+
+#[derive(Debug)]
+struct Order {
+    id: u32,
+    amount: i64,
+}
+
+fn charge(order: Order) -> Result<String> {
+    if order.amount <= 0 {
+        return err!("invalid amount {}", order.amount);
+    }
+
+    Ok(format!("receipt for {}", order.id))
+}
+
+#[test]
+fn and_then_ctx_success() {
+    let result: Result<Order> = Ok(Order { id: 1, amount: 10 });
+    let receipt = result
+        .and_then_ctx(|order| format!("charging order {}", order.id), charge)
+        .unwrap();
+
+    assert_eq!(receipt, "receipt for 1");
+}
+
+#[test]
+fn and_then_ctx_failure_includes_intermediate_value() {
+    let result: Result<Order> = Ok(Order { id: 7, amount: -5 });
+    let err = result
+        .and_then_ctx(
+            |order| format!("charging order {} ({})", order.id, order.amount),
+            charge,
+        )
+        .unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["charging order 7 (-5)", "invalid amount -5"]);
+}
+
+#[test]
+fn and_then_ctx_skips_step_on_err() {
+    let described = Cell::new(false);
+    let called = Cell::new(false);
+
+    let result: Result<Order> = Err(anyerr!("order not found"));
+    let err = result
+        .and_then_ctx(
+            |order| {
+                described.set(true);
+                order.id
+            },
+            |order| {
+                called.set(true);
+                charge(order)
+            },
+        )
+        .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "order not found");
+    assert!(!described.get());
+    assert!(!called.get());
+}
@@ -0,0 +1,43 @@
+//! Tests for anyerr_chain()
+
+use okerr::anyerr_chain;
+
+fn chain_of(err: &okerr::Error) -> Vec<String> {
+    err.chain().map(|cause| cause.to_string()).collect()
+}
+
+#[test]
+fn anyerr_chain_order() {
+    let messages = ["disk full", "writing cache", "saving session"].map(String::from);
+    let err = anyerr_chain(messages);
+
+    // From the top-level message to the root cause
+    assert_eq!(
+        chain_of(&err),
+        ["saving session", "writing cache", "disk full"]
+    );
+    assert_eq!(err.to_string(), "saving session");
+    assert_eq!(err.root_cause().to_string(), "disk full");
+}
+
+#[test]
+fn anyerr_chain_single_message() {
+    let err = anyerr_chain(vec!["only".to_string()]);
+
+    assert_eq!(chain_of(&err), ["only"]);
+}
+
+#[test]
+fn anyerr_chain_from_iterator() {
+    let err = anyerr_chain((1..=3).map(|i| format!("level {i}")));
+
+    assert_eq!(chain_of(&err), ["level 3", "level 2", "level 1"]);
+}
+
+#[test]
+fn anyerr_chain_empty() {
+    let err = anyerr_chain(Vec::new());
+
+    assert_eq!(err.to_string(), "");
+    assert_eq!(err.chain().count(), 1);
+}
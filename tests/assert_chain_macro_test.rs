@@ -0,0 +1,54 @@
+//! Tests for assert_chain! macro
+
+use okerr::{Context, Result, anyerr, assert_chain};
+use std::io;
+
+fn failing() -> Result<()> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "config.toml"))
+        .context("reading config")
+        .context("starting app")
+}
+
+#[test]
+fn assert_chain_matching() {
+    assert_chain!(failing(), ["starting app", "reading config", "config.toml"]);
+}
+
+#[test]
+fn assert_chain_with_std_error() {
+    let result: std::result::Result<(), io::Error> = Err(io::Error::other("disk"));
+    assert_chain!(result, ["disk"]);
+}
+
+#[test]
+fn assert_chain_with_vec() {
+    let expected = vec!["starting app", "reading config", "config.toml"];
+    assert_chain!(failing(), expected);
+}
+
+#[test]
+#[should_panic(expected = "level 1: expected \"loading config\", got \"reading config\"")]
+fn assert_chain_mismatch() {
+    assert_chain!(failing(), ["starting app", "loading config", "config.toml"]);
+}
+
+#[test]
+#[should_panic(expected = "level 2: expected \"config.toml\", got nothing")]
+fn assert_chain_too_short() {
+    let result: Result<()> = Err(anyerr!("reading config")).context("starting app");
+    assert_chain!(result, ["starting app", "reading config", "config.toml"]);
+}
+
+#[test]
+#[should_panic(expected = "level 1: unexpected \"reading config\"")]
+fn assert_chain_too_long() {
+    let result: Result<()> = Err(anyerr!("reading config")).context("starting app");
+    assert_chain!(result, ["starting app"]);
+}
+
+#[test]
+#[should_panic(expected = "expected an error chain, got Ok")]
+fn assert_chain_ok() {
+    let result: Result<i32> = Ok(1);
+    assert_chain!(result, ["anything"]);
+}
@@ -0,0 +1,59 @@
+//! Tests for bail_all! macro
+
+use okerr::{MultiError, Result, bail_all};
+use std::cell::Cell;
+
+fn validate(name: &str, age: i32, email: &str) -> Result<&'static str> {
+    bail_all!([
+        name.is_empty() => "name is empty",
+        age < 0 => format!("age is negative: {age}"),
+        !email.contains('@') => "email is invalid",
+    ]);
+
+    Ok("valid")
+}
+
+#[test]
+fn bail_all_all_false_continues() {
+    assert_eq!(validate("alice", 30, "alice@example.com").unwrap(), "valid");
+}
+
+#[test]
+fn bail_all_single_true() {
+    let err = validate("alice", 30, "nope").unwrap_err();
+
+    assert_eq!(err.to_string(), "1 error occurred:\n  - email is invalid");
+}
+
+#[test]
+fn bail_all_multiple_true_combined() {
+    let err = validate("", -3, "nope").unwrap_err();
+
+    let multi = err.downcast_ref::<MultiError>().unwrap();
+    let messages: Vec<String> = multi.errors().iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["name is empty", "age is negative: -3", "email is invalid"]
+    );
+}
+
+#[test]
+fn bail_all_evaluates_every_condition() {
+    let evaluated = Cell::new(0);
+    let check = |result: bool| {
+        evaluated.set(evaluated.get() + 1);
+        result
+    };
+
+    let run = || -> Result<()> {
+        bail_all!([
+            check(true) => "first",
+            check(false) => "second",
+            check(true) => "third",
+        ]);
+        Ok(())
+    };
+
+    assert!(run().is_err());
+    assert_eq!(evaluated.get(), 3);
+}
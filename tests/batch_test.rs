@@ -0,0 +1,102 @@
+//! Tests for batch::process_batch()
+
+use okerr::batch::process_batch;
+use okerr::{Context, Result, ensure};
+use std::cell::Cell;
+
+fn parse(s: &&str) -> Result<u8> {
+    s.parse::<u8>()
+        .with_context(|| format!("invalid value {s:?}"))
+}
+
+#[test]
+fn all_ok_in_both_modes() {
+    let items = ["1", "2", "3"];
+
+    assert_eq!(process_batch(&items, parse, true).unwrap(), [1, 2, 3]);
+    assert_eq!(process_batch(&items, parse, false).unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn stop_on_error_returns_first_failure() {
+    let processed = Cell::new(0);
+    let items = ["1", "x", "3", "y"];
+
+    let err = process_batch(
+        &items,
+        |s| {
+            processed.set(processed.get() + 1);
+            parse(s)
+        },
+        true,
+    )
+    .unwrap_err();
+
+    assert_eq!(processed.get(), 2);
+    assert_eq!(err.indices(), [1]);
+    assert_eq!(
+        err.to_string(),
+        "1 error occurred:\n  - item 1: invalid value \"x\": invalid digit found in string"
+    );
+}
+
+#[test]
+fn continue_reports_every_failure() {
+    let processed = Cell::new(0);
+    let items = ["x", "2", "300", "4", "y"];
+
+    let err = process_batch(
+        &items,
+        |s| {
+            processed.set(processed.get() + 1);
+            parse(s)
+        },
+        false,
+    )
+    .unwrap_err();
+
+    assert_eq!(processed.get(), 5);
+    assert_eq!(err.indices(), [0, 2, 4]);
+
+    let messages: Vec<String> = err
+        .failures()
+        .iter()
+        .map(|(_, err)| err.to_string())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "invalid value \"x\"",
+            "invalid value \"300\"",
+            "invalid value \"y\""
+        ]
+    );
+}
+
+#[test]
+fn batch_error_into_failures() {
+    let err = process_batch(
+        &[0, 5],
+        |n| {
+            ensure!(*n > 0, "zero");
+            Ok(*n)
+        },
+        false,
+    )
+    .unwrap_err();
+
+    let failures = err.into_failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, 0);
+    assert_eq!(failures[0].1.to_string(), "zero");
+}
+
+#[test]
+fn batch_error_converts_into_error() {
+    fn run() -> Result<Vec<u8>> {
+        Ok(process_batch(&["a"], parse, true)?)
+    }
+
+    let err = run().unwrap_err();
+    assert!(err.downcast_ref::<okerr::batch::BatchError>().is_some());
+}
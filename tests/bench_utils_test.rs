@@ -0,0 +1,29 @@
+//! Tests for bench::make_chain() and bench::make_wide()
+
+#![cfg(feature = "bench-utils")]
+
+use okerr::bench::{make_chain, make_wide};
+
+#[test]
+fn make_chain_depth() {
+    let err = make_chain(5);
+
+    assert_eq!(err.chain().count(), 5);
+    assert_eq!(err.to_string(), "level 4");
+    assert_eq!(err.root_cause().to_string(), "level 0");
+}
+
+#[test]
+fn make_chain_at_least_one_level() {
+    assert_eq!(make_chain(0).chain().count(), 1);
+    assert_eq!(make_chain(1).chain().count(), 1);
+}
+
+#[test]
+fn make_wide_count() {
+    let multi = make_wide(4);
+
+    assert_eq!(multi.len(), 4);
+    assert_eq!(multi.errors()[3].to_string(), "error 3");
+    assert!(make_wide(0).is_empty());
+}
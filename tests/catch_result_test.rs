@@ -0,0 +1,40 @@
+//! Tests for catch_result()
+
+use okerr::{Context, Result, anyerr, catch_result};
+
+#[test]
+fn catch_result_ok() {
+    assert_eq!(catch_result(|| Ok(42)).unwrap(), 42);
+}
+
+#[test]
+fn catch_result_returned_err() {
+    let result: Result<()> = catch_result(|| Err(anyerr!("disk full")).context("saving"));
+    assert_eq!(format!("{:#}", result.unwrap_err()), "saving: disk full");
+}
+
+#[test]
+fn catch_result_panic_with_str() {
+    let result: Result<()> = catch_result(|| panic!("boom"));
+    assert_eq!(result.unwrap_err().to_string(), "panicked: boom");
+}
+
+#[test]
+fn catch_result_panic_with_formatted_message() {
+    let values: Vec<u8> = Vec::new();
+    let index = 3;
+
+    let result: Result<u8> = catch_result(|| {
+        if values.is_empty() {
+            panic!("no value at {index}");
+        }
+        Ok(values[0])
+    });
+    assert_eq!(result.unwrap_err().to_string(), "panicked: no value at 3");
+}
+
+#[test]
+fn catch_result_panic_with_other_payload() {
+    let result: Result<()> = catch_result(|| std::panic::panic_any(7_u32));
+    assert_eq!(result.unwrap_err().to_string(), "panicked: Box<dyn Any>");
+}
@@ -0,0 +1,60 @@
+//! Tests for category::category() and category::auto_hint()
+
+use okerr::category::{Category, auto_hint, category};
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::io;
+use std::sync::atomic::AtomicBool;
+
+fn io_failure(kind: io::ErrorKind) -> okerr::Error {
+    let result: Result<()> = Err(io::Error::new(kind, "os error"))
+        .context("opening file")
+        .context("loading");
+
+    result.unwrap_err()
+}
+
+#[test]
+fn auto_hint_permission_denied() {
+    let err = io_failure(io::ErrorKind::PermissionDenied);
+
+    assert_eq!(category(&err), Some(Category::PermissionDenied));
+    assert_eq!(
+        auto_hint(&err).as_deref(),
+        Some("try running with elevated privileges")
+    );
+}
+
+#[test]
+fn auto_hint_uncategorizable() {
+    assert_eq!(category(&anyerr!("something odd")), None);
+    assert_eq!(auto_hint(&anyerr!("something odd")), None);
+    assert_eq!(auto_hint(&io_failure(io::ErrorKind::Other)), None);
+}
+
+#[test]
+fn category_from_io_kinds() {
+    for (kind, expected) in [
+        (io::ErrorKind::NotFound, Category::NotFound),
+        (io::ErrorKind::AlreadyExists, Category::AlreadyExists),
+        (io::ErrorKind::InvalidData, Category::InvalidInput),
+        (io::ErrorKind::TimedOut, Category::Timeout),
+        (io::ErrorKind::ConnectionRefused, Category::Network),
+        (io::ErrorKind::Interrupted, Category::Interrupted),
+    ] {
+        let err = io_failure(kind);
+
+        assert_eq!(category(&err), Some(expected));
+        assert!(auto_hint(&err).is_some());
+    }
+}
+
+#[test]
+fn category_interrupted() {
+    let result: Result<()> = Err(anyerr!("broken pipe"));
+    let err = result
+        .or_interrupt(&AtomicBool::new(true))
+        .context("copying")
+        .unwrap_err();
+
+    assert_eq!(category(&err), Some(Category::Interrupted));
+}
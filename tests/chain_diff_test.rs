@@ -0,0 +1,56 @@
+//! Tests for chains_equal() and chain_diff()
+
+use okerr::{anyerr, anyerr_chain, chain_diff, chains_equal};
+
+fn chain(messages: &[&str]) -> okerr::Error {
+    // From the root cause to the top-level message
+    anyerr_chain(messages.iter().map(|msg| msg.to_string()))
+}
+
+#[test]
+fn chains_equal_same_messages() {
+    let a = chain(&["root", "middle", "outer"]);
+    let b = chain(&["root", "middle", "outer"]);
+
+    assert!(chains_equal(&a, &b));
+    assert!(chain_diff(&a, &b).is_empty());
+}
+
+#[test]
+fn chains_equal_ignores_types() {
+    let a = anyerr!(std::io::Error::other("disk")).context("saving");
+    let b = chain(&["disk", "saving"]);
+
+    assert!(chains_equal(&a, &b));
+}
+
+#[test]
+fn chain_diff_reports_exact_levels() {
+    let a = chain(&["root A", "middle", "outer A"]);
+    let b = chain(&["root B", "middle", "outer B"]);
+
+    assert!(!chains_equal(&a, &b));
+    assert_eq!(
+        chain_diff(&a, &b),
+        [
+            (0, "outer A".to_string(), "outer B".to_string()),
+            (2, "root A".to_string(), "root B".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn chain_diff_different_depths() {
+    let a = chain(&["root", "outer"]);
+    let b = chain(&["cause", "root", "outer"]);
+
+    assert!(!chains_equal(&a, &b));
+    assert_eq!(
+        chain_diff(&a, &b),
+        [(2, String::new(), "cause".to_string())]
+    );
+    assert_eq!(
+        chain_diff(&b, &a),
+        [(2, "cause".to_string(), String::new())]
+    );
+}
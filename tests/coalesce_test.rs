@@ -0,0 +1,68 @@
+//! Tests for coalesce() and MultiError
+
+use okerr::{Context, MultiError, Result, anyerr, coalesce, err};
+use std::cell::Cell;
+
+#[test]
+fn coalesce_first_success_short_circuits() {
+    let calls = Cell::new(0);
+
+    let result: Result<i32> = coalesce(vec![
+        Box::new(|| err!("first failed")),
+        Box::new(|| Ok(2)),
+        Box::new(|| {
+            calls.set(calls.get() + 1);
+            Ok(3)
+        }),
+    ]);
+
+    assert_eq!(result.unwrap(), 2);
+    assert_eq!(calls.get(), 0);
+}
+
+#[test]
+fn coalesce_all_fail_surfaces_every_error() {
+    let result: Result<i32> = coalesce(vec![
+        Box::new(|| err!("env not set")),
+        Box::new(|| Err(anyerr!("no such file")).context("reading config.toml")),
+        Box::new(|| err!("no defaults")),
+    ]);
+
+    let err = result.unwrap_err();
+    let multi = err.downcast_ref::<MultiError>().unwrap();
+    assert_eq!(multi.len(), 3);
+
+    assert_eq!(
+        err.to_string(),
+        "3 errors occurred:\n  \
+         - env not set\n  \
+         - reading config.toml: no such file\n  \
+         - no defaults"
+    );
+}
+
+#[test]
+fn coalesce_empty() {
+    let result: Result<i32> = coalesce(Vec::new());
+
+    let err = result.unwrap_err();
+    assert!(err.downcast_ref::<MultiError>().unwrap().is_empty());
+}
+
+#[test]
+fn multi_error_collects() {
+    let mut multi: MultiError = vec![anyerr!("a")].into();
+    multi.push(anyerr!("b"));
+
+    assert_eq!(multi.len(), 2);
+    assert_eq!(multi.errors()[1].to_string(), "b");
+
+    let multi: MultiError = multi.into_errors().into_iter().collect();
+    assert_eq!(multi.len(), 2);
+}
+
+#[test]
+fn multi_error_single_display() {
+    let multi = MultiError::from(vec![anyerr!("alone")]);
+    assert_eq!(multi.to_string(), "1 error occurred:\n  - alone");
+}
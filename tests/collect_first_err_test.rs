@@ -0,0 +1,63 @@
+//! Tests for convert::ResultIterExt::collect_first_err()
+
+use okerr::{Result, anyerr, convert::ResultIterExt};
+
+#[test]
+fn collect_first_err_all_ok() {
+    let values = (1..=3).map(Ok).collect_first_err().unwrap();
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn collect_first_err_empty() {
+    let values = std::iter::empty::<Result<u8>>()
+        .collect_first_err()
+        .unwrap();
+    assert!(values.is_empty());
+}
+
+#[test]
+fn collect_first_err_reports_success_count() {
+    let results = vec![Ok(1), Ok(2), Ok(3), Err(anyerr!("row 4 invalid")), Ok(5)];
+
+    let err = results.into_iter().collect_first_err().unwrap_err();
+    assert_eq!(
+        format!("{err:#}"),
+        "stopped after 3 successes: row 4 invalid"
+    );
+}
+
+#[test]
+fn collect_first_err_stops_at_first_error() {
+    let mut processed = 0;
+
+    let err = (0..10)
+        .map(|i| {
+            processed += 1;
+            if i >= 2 {
+                return Err(anyerr!("failed at {i}"));
+            }
+            Ok(i)
+        })
+        .collect_first_err()
+        .unwrap_err();
+
+    assert_eq!(processed, 3);
+    assert_eq!(err.root_cause().to_string(), "failed at 2");
+    assert_eq!(err.to_string(), "stopped after 2 successes");
+}
+
+#[test]
+fn collect_first_err_singular_and_zero() {
+    let err = vec![Ok(1), Err(anyerr!("boom"))]
+        .into_iter()
+        .collect_first_err()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "stopped after 1 success");
+
+    let err = vec![Err::<u8, _>(anyerr!("boom"))]
+        .into_iter()
+        .collect_first_err()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "stopped after 0 successes");
+}
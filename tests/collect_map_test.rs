@@ -0,0 +1,48 @@
+//! Tests for collect_map()
+
+use okerr::{Result, anyerr, collect_map};
+use std::collections::HashMap;
+
+fn parse(line: &str) -> Result<(String, u32)> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| anyerr!("missing ':' in {line:?}"))?;
+    Ok((key.trim().to_string(), value.trim().parse()?))
+}
+
+#[test]
+fn collects_all_entries() {
+    let map = collect_map(["a: 1", "b: 2", "c: 3"].map(parse)).unwrap();
+
+    assert_eq!(
+        map,
+        HashMap::from([
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 3),
+        ])
+    );
+}
+
+#[test]
+fn empty_input() {
+    let map = collect_map(Vec::<Result<(u8, u8)>>::new()).unwrap();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn failing_entry_is_numbered() {
+    let err = collect_map(["a: 1", "b: 2", "oops", "d: 4"].map(parse)).unwrap_err();
+
+    assert_eq!(
+        format!("{err:#}"),
+        "entry #2 failed: missing ':' in \"oops\""
+    );
+}
+
+#[test]
+fn duplicate_key_collision() {
+    let err = collect_map(["a: 1", "b: 2", "c: 3", "b: 20"].map(parse)).unwrap_err();
+
+    assert_eq!(err.to_string(), "entry #3 duplicates the key of entry #1");
+}
@@ -0,0 +1,61 @@
+//! Tests for compat::to_std() and compat::from_std()
+
+use okerr::compat::{BoxError, from_std, to_std};
+use okerr::{Context, Result, anyerr};
+use std::io;
+
+fn chain_of(err: &(dyn std::error::Error + 'static)) -> Vec<String> {
+    let mut chain = vec![err.to_string()];
+    let mut source = err.source();
+
+    while let Some(cause) = source {
+        chain.push(cause.to_string());
+        source = cause.source();
+    }
+
+    chain
+}
+
+#[test]
+fn to_std_preserves_display_and_chain() {
+    let result: Result<()> = Err(anyerr!("connection refused")).context("fetching");
+    let boxed = to_std(result).unwrap_err();
+
+    assert_eq!(boxed.to_string(), "fetching");
+    assert_eq!(chain_of(boxed.as_ref()), ["fetching", "connection refused"]);
+}
+
+#[test]
+fn to_std_ok() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(to_std(result).unwrap(), 1);
+}
+
+#[test]
+fn from_std_boxed_error() {
+    let result: std::result::Result<(), BoxError> = Err("plain message".into());
+    let err = from_std(result).unwrap_err();
+
+    assert_eq!(err.to_string(), "plain message");
+}
+
+#[test]
+fn from_std_any_std_error() {
+    let result: std::result::Result<(), io::Error> = Err(io::Error::other("io failure"));
+    let err = from_std(result).unwrap_err();
+
+    assert_eq!(err.to_string(), "io failure");
+}
+
+#[test]
+fn round_trip_preserves_display() {
+    let original: Result<()> = Err(anyerr!("root")).context("middle").context("outer");
+
+    let err = from_std(to_std(original)).unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "outer: middle: root");
+    assert_eq!(err.chain().count(), 3);
+
+    let boxed: std::result::Result<i32, BoxError> = Ok(5);
+    assert_eq!(to_std(from_std(boxed)).unwrap(), 5);
+}
@@ -0,0 +1,88 @@
+//! Tests for CallerFrame and ResultExt::context_callers()
+
+use okerr::{CallerFrame, Result, ResultExt, anyerr};
+
+fn inner() -> (Result<()>, u32) {
+    let line = line!() + 1;
+    let _frame = CallerFrame::enter("inner");
+    (Err(anyerr!("timeout")).context_callers(), line)
+}
+
+fn outer() -> (Result<()>, u32, u32) {
+    let line = line!() + 1;
+    let _frame = CallerFrame::enter("outer");
+    let (result, inner_line) = inner();
+    (result, line, inner_line)
+}
+
+#[test]
+fn context_callers_nested_frames_in_order() {
+    let (result, outer_line, inner_line) = outer();
+    let err = result.unwrap_err();
+
+    let file = "tests/context_callers_test.rs";
+    let frames: Vec<String> = err.to_string().split(" > ").map(String::from).collect();
+
+    assert_eq!(frames.len(), 2);
+    assert!(
+        frames[0].starts_with(&format!("outer ({file}:{outer_line}:")),
+        "{frames:?}"
+    );
+    assert!(
+        frames[1].starts_with(&format!("inner ({file}:{inner_line}:")),
+        "{frames:?}"
+    );
+    assert_eq!(err.root_cause().to_string(), "timeout");
+}
+
+#[test]
+fn frames_are_popped_on_drop() {
+    {
+        let _frame = CallerFrame::enter("temporary");
+    }
+
+    let result: Result<()> = Err(anyerr!("boom"));
+    let err = result.context_callers().unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+    assert_eq!(err.to_string(), "boom");
+}
+
+#[test]
+fn frames_dropped_out_of_order() {
+    let first = CallerFrame::enter("first");
+    let second = CallerFrame::enter("second");
+
+    // Dropping the first frame also pops the second one
+    drop(first);
+
+    let result: Result<()> = Err(anyerr!("boom"));
+    assert_eq!(result.context_callers().unwrap_err().to_string(), "boom");
+
+    drop(second);
+
+    let _frame = CallerFrame::enter("next");
+    let result: Result<()> = Err(anyerr!("boom"));
+    let err = result.context_callers().unwrap_err();
+    assert!(err.to_string().starts_with("next ("), "{err}");
+}
+
+#[test]
+fn frames_are_per_thread() {
+    let _frame = CallerFrame::enter("main_thread");
+
+    let err = std::thread::spawn(|| {
+        let result: Result<()> = Err(anyerr!("boom"));
+        result.context_callers().unwrap_err().to_string()
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(err, "boom");
+}
+
+#[test]
+fn context_callers_keeps_ok() {
+    let _frame = CallerFrame::enter("ok");
+    let result: Result<u8> = Ok(1);
+    assert_eq!(result.context_callers().unwrap(), 1);
+}
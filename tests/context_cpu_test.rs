@@ -0,0 +1,39 @@
+//! Tests for timing::context_cpu()
+
+#![cfg(target_os = "linux")]
+
+use okerr::{Result, anyerr, timing::context_cpu};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[test]
+fn error_gets_cpu_time() {
+    let result: Result<()> = context_cpu(|| {
+        let start = Instant::now();
+        let mut n: u64 = 0;
+
+        while start.elapsed() < Duration::from_millis(20) {
+            n = black_box(n.wrapping_add(1));
+        }
+
+        Err(anyerr!("hash mismatch"))
+    });
+
+    let err = result.unwrap_err();
+    let ms = err
+        .to_string()
+        .strip_prefix("cpu_time=")
+        .and_then(|rest| rest.strip_suffix("ms"))
+        .map(|ms| ms.parse::<u128>())
+        .expect("cpu_time context")
+        .unwrap();
+
+    assert!(ms < 60_000);
+    assert_eq!(err.root_cause().to_string(), "hash mismatch");
+}
+
+#[test]
+fn ok_passes_through() {
+    let result: Result<u8> = context_cpu(|| Ok(7));
+    assert_eq!(result.unwrap(), 7);
+}
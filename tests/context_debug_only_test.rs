@@ -0,0 +1,35 @@
+//! Tests for ResultExt::context_debug_only()
+
+use okerr::{Result, ResultExt, anyerr};
+
+#[test]
+fn context_debug_only_in_current_build() {
+    let result: Result<()> = Err(anyerr!("parse failed"));
+    let err = result
+        .context_debug_only("input buffer: [0x00, 0xff]")
+        .unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+
+    if cfg!(debug_assertions) {
+        assert_eq!(chain, ["input buffer: [0x00, 0xff]", "parse failed"]);
+    } else {
+        // Release build: unchanged
+        assert_eq!(chain, ["parse failed"]);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn context_debug_only_present_in_debug() {
+    let result: Result<()> = Err(anyerr!("parse failed"));
+    let err = result.context_debug_only("debug details").unwrap_err();
+
+    assert_eq!(err.to_string(), "debug details");
+}
+
+#[test]
+fn context_debug_only_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(result.context_debug_only("unused").unwrap(), 1);
+}
@@ -0,0 +1,48 @@
+//! Tests for ResultExt::context_deferred()
+
+use okerr::{Result, ResultExt, anyerr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn counted(calls: &Arc<AtomicUsize>) -> impl Fn() -> String + Send + Sync + 'static {
+    let calls = Arc::clone(calls);
+
+    move || {
+        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("expensive context #{n}")
+    }
+}
+
+#[test]
+fn context_deferred_not_evaluated_when_attached() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let result: Result<()> = Err(anyerr!("fail"));
+
+    let err = result.context_deferred(counted(&calls)).unwrap_err();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    drop(err);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn context_deferred_evaluated_once_per_format() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let result: Result<()> = Err(anyerr!("fail"));
+    let err = result.context_deferred(counted(&calls)).unwrap_err();
+
+    assert_eq!(err.to_string(), "expensive context #1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    assert_eq!(format!("{err:#}"), "expensive context #2: fail");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn context_deferred_ok_never_evaluated() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let result: Result<i32> = Ok(1);
+
+    assert_eq!(result.context_deferred(counted(&calls)).unwrap(), 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,37 @@
+//! Tests for ResultExt::context_env_prefixed()
+
+use okerr::{Result, ResultExt, anyerr};
+
+// All the environment accesses of this test binary are in this test:
+// `set_var` must not run while another thread reads the environment.
+#[test]
+fn only_prefixed_vars_sorted() {
+    // SAFETY: no other thread of this test binary reads or writes the environment.
+    unsafe {
+        std::env::set_var("OKERR_ENV_TEST_B", "2");
+        std::env::set_var("OKERR_ENV_TEST_A", "1");
+        std::env::set_var("OKERR_UNRELATED_TEST", "3");
+    }
+
+    let result: Result<()> = Err(anyerr!("config load failed"));
+    let err = result.context_env_prefixed("OKERR_ENV_TEST_").unwrap_err();
+
+    assert_eq!(err.to_string(), "OKERR_ENV_TEST_A=1, OKERR_ENV_TEST_B=2");
+    assert!(!format!("{err:#}").contains("OKERR_UNRELATED_TEST"));
+    assert_eq!(err.root_cause().to_string(), "config load failed");
+
+    // No match: the error is unchanged
+    let result: Result<()> = Err(anyerr!("config load failed"));
+    let err = result
+        .context_env_prefixed("OKERR_NO_SUCH_PREFIX_")
+        .unwrap_err();
+
+    assert_eq!(err.chain().count(), 1);
+    assert_eq!(err.to_string(), "config load failed");
+}
+
+#[test]
+fn ok_passes_through() {
+    let result: Result<u8> = Ok(7);
+    assert_eq!(result.context_env_prefixed("OKERR_").unwrap(), 7);
+}
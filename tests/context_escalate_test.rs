@@ -0,0 +1,67 @@
+//! Tests for RetryEscalator and ResultExt::context_escalate()
+
+use okerr::{Result, ResultExt, RetryEscalator, anyerr};
+
+fn failing() -> Result<()> {
+    Err(anyerr!("connection refused"))
+}
+
+#[test]
+fn escalates_only_after_threshold() {
+    let mut esc = RetryEscalator::new(4);
+
+    let messages: Vec<String> = (0..5)
+        .map(|_| format!("{:#}", failing().context_escalate(&mut esc).unwrap_err()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        [
+            "connection refused",
+            "failed 2 times in a row: connection refused",
+            "failed 3 times in a row: connection refused",
+            "persistent failure (4 times in a row), consider alerting: connection refused",
+            "persistent failure (5 times in a row), consider alerting: connection refused",
+        ]
+    );
+    assert_eq!(esc.failures(), 5);
+}
+
+#[test]
+fn success_resets_counter() {
+    let mut esc = RetryEscalator::default();
+
+    for _ in 0..3 {
+        let _ = failing().context_escalate(&mut esc);
+    }
+    assert_eq!(esc.failures(), 3);
+
+    let ok: Result<u8> = Ok(1);
+    assert_eq!(ok.context_escalate(&mut esc).unwrap(), 1);
+    assert_eq!(esc.failures(), 0);
+
+    let err = failing().context_escalate(&mut esc).unwrap_err();
+    assert_eq!(format!("{err:#}"), "connection refused");
+}
+
+#[test]
+fn default_threshold_is_three() {
+    let mut esc = RetryEscalator::default();
+
+    let _ = failing().context_escalate(&mut esc);
+    let second = failing().context_escalate(&mut esc).unwrap_err();
+    let third = failing().context_escalate(&mut esc).unwrap_err();
+
+    assert_eq!(second.to_string(), "failed 2 times in a row");
+    assert!(third.to_string().starts_with("persistent failure"));
+}
+
+#[test]
+fn manual_reset() {
+    let mut esc = RetryEscalator::new(2);
+    let _ = failing().context_escalate(&mut esc);
+    esc.reset();
+
+    let err = failing().context_escalate(&mut esc).unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+}
@@ -0,0 +1,65 @@
+//! Tests for ResultExt::context_flag()
+
+use okerr::{Result, ResultExt, anyerr};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Counts how many times it's formatted.
+struct Counted(Arc<AtomicUsize>);
+
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        f.write_str("request body: {...}")
+    }
+}
+
+fn failing() -> Result<()> {
+    Err(anyerr!("invalid payload"))
+}
+
+#[test]
+fn context_toggles_with_flag() {
+    let flag = AtomicBool::new(false);
+
+    let err = failing().context_flag(&flag, "verbose").unwrap_err();
+    assert_eq!(format!("{err:#}"), "invalid payload");
+
+    flag.store(true, Ordering::Relaxed);
+    let err = failing().context_flag(&flag, "verbose").unwrap_err();
+    assert_eq!(format!("{err:#}"), "verbose: invalid payload");
+
+    flag.store(false, Ordering::Relaxed);
+    let err = failing().context_flag(&flag, "verbose").unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn message_formatted_only_when_added() {
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let err = failing()
+        .context_flag(&VERBOSE, Counted(count.clone()))
+        .unwrap_err();
+    let _ = format!("{err:#}");
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    let flag = AtomicBool::new(true);
+    let err = failing()
+        .context_flag(&flag, Counted(count.clone()))
+        .unwrap_err();
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+
+    assert_eq!(err.to_string(), "request body: {...}");
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn context_flag_keeps_ok() {
+    let flag = AtomicBool::new(true);
+    let result: Result<u8> = Ok(1);
+    assert_eq!(result.context_flag(&flag, "verbose").unwrap(), 1);
+}
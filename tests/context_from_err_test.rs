@@ -0,0 +1,50 @@
+//! Tests for ResultExt::context_from_err()
+
+use okerr::{Context, Result, ResultExt, anyerr};
+
+fn cleanup_error() -> okerr::Error {
+    anyerr!("permission denied").context("removing /tmp/upload.part")
+}
+
+#[test]
+fn context_from_err_adds_other_chain() {
+    let cleanup = cleanup_error();
+    let result: Result<()> = Err(anyerr!("upload interrupted"));
+
+    let err = result.context_from_err(&cleanup).unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(
+        chain,
+        [
+            "removing /tmp/upload.part: permission denied",
+            "upload interrupted"
+        ]
+    );
+}
+
+#[test]
+fn context_from_err_does_not_consume_other() {
+    let cleanup = cleanup_error();
+
+    let first: Result<()> = Err(anyerr!("first"));
+    let second: Result<()> = Err(anyerr!("second"));
+    let first = first.context_from_err(&cleanup).unwrap_err();
+    let second = second
+        .context_from_err(&cleanup)
+        .context("outer")
+        .unwrap_err();
+
+    assert_eq!(first.to_string(), format!("{cleanup:#}"));
+    assert_eq!(
+        second.chain().nth(1).unwrap().to_string(),
+        format!("{cleanup:#}")
+    );
+    assert_eq!(cleanup.chain().count(), 2);
+}
+
+#[test]
+fn context_from_err_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(result.context_from_err(&cleanup_error()).unwrap(), 1);
+}
@@ -0,0 +1,61 @@
+//! Tests for ResultExt::context_map() and context_metadata()
+
+use okerr::{Context, Result, ResultExt, anyerr, context_metadata};
+use std::collections::HashMap;
+
+fn metadata() -> HashMap<String, String> {
+    HashMap::from([
+        ("path".to_string(), "/users".to_string()),
+        ("method".to_string(), "GET".to_string()),
+        ("user_id".to_string(), "42".to_string()),
+    ])
+}
+
+#[test]
+fn context_map_display_is_sorted() {
+    let result: Result<()> = Err(anyerr!("handler failed"));
+    let err = result.context_map(&metadata()).unwrap_err();
+
+    assert_eq!(err.to_string(), "method=GET path=/users user_id=42");
+    assert_eq!(
+        format!("{err:#}"),
+        "method=GET path=/users user_id=42: handler failed"
+    );
+}
+
+#[test]
+fn context_map_is_deterministic() {
+    let result: Result<()> = Err(anyerr!("a"));
+    let first = result.context_map(&metadata()).unwrap_err().to_string();
+
+    for _ in 0..10 {
+        let result: Result<()> = Err(anyerr!("a"));
+        assert_eq!(
+            result.context_map(&metadata()).unwrap_err().to_string(),
+            first
+        );
+    }
+}
+
+#[test]
+fn context_metadata_recovers_map() {
+    let result: Result<()> = Err(anyerr!("handler failed"));
+    let err = result
+        .context_map(&metadata())
+        .context("serving request")
+        .unwrap_err();
+
+    assert_eq!(context_metadata(&err), metadata());
+}
+
+#[test]
+fn context_metadata_empty_without_map() {
+    let err = anyerr!("plain");
+    assert!(context_metadata(&err).is_empty());
+}
+
+#[test]
+fn context_map_ok_passes_through() {
+    let result: Result<i32> = Ok(3);
+    assert_eq!(result.context_map(&metadata()).unwrap(), 3);
+}
@@ -0,0 +1,34 @@
+//! Tests for ResultExt::context_mem()
+
+use okerr::{Result, ResultExt, anyerr};
+
+#[cfg(target_os = "linux")]
+#[test]
+fn context_mem_attaches_rss() {
+    let result: Result<()> = Err(anyerr!("allocation failed"));
+    let err = result.context_mem().unwrap_err();
+
+    let msg = err.to_string();
+    let kb = msg
+        .strip_prefix("rss=")
+        .and_then(|rest| rest.strip_suffix("KB"))
+        .expect("rss context");
+    assert!(kb.parse::<u64>().unwrap() > 0);
+    assert_eq!(err.root_cause().to_string(), "allocation failed");
+}
+
+#[cfg(not(target_os = "linux"))]
+#[test]
+fn context_mem_unsupported_is_noop() {
+    let result: Result<()> = Err(anyerr!("allocation failed"));
+    let err = result.context_mem().unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "allocation failed");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn context_mem_keeps_ok() {
+    let result: Result<u8> = Ok(3);
+    assert_eq!(result.context_mem().unwrap(), 3);
+}
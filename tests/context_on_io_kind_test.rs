@@ -0,0 +1,64 @@
+//! Tests for ResultExt::context_on_io_kind()
+
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::io;
+
+fn io_failure(kind: io::ErrorKind) -> Result<()> {
+    Err(io::Error::new(kind, "os error")).context("opening data.db")
+}
+
+fn chain_of(err: &okerr::Error) -> Vec<String> {
+    err.chain().map(|cause| cause.to_string()).collect()
+}
+
+#[test]
+fn context_on_io_kind_matching_kind() {
+    let err = io_failure(io::ErrorKind::PermissionDenied)
+        .context_on_io_kind(io::ErrorKind::PermissionDenied, "check permissions")
+        .unwrap_err();
+
+    assert_eq!(
+        chain_of(&err),
+        ["check permissions", "opening data.db", "os error"]
+    );
+}
+
+#[test]
+fn context_on_io_kind_other_kind() {
+    let err = io_failure(io::ErrorKind::NotFound)
+        .context_on_io_kind(io::ErrorKind::PermissionDenied, "check permissions")
+        .unwrap_err();
+
+    assert_eq!(chain_of(&err), ["opening data.db", "os error"]);
+}
+
+#[test]
+fn context_on_io_kind_non_io_error() {
+    let result: Result<()> = Err(anyerr!("not io"));
+    let err = result
+        .context_on_io_kind(io::ErrorKind::PermissionDenied, "check permissions")
+        .unwrap_err();
+
+    assert_eq!(chain_of(&err), ["not io"]);
+}
+
+#[test]
+fn context_on_io_kind_direct_io_error() {
+    let result: Result<()> = Err(io::Error::from(io::ErrorKind::NotFound).into());
+    let err = result
+        .context_on_io_kind(io::ErrorKind::NotFound, "is the path correct?")
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "is the path correct?");
+}
+
+#[test]
+fn context_on_io_kind_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(
+        result
+            .context_on_io_kind(io::ErrorKind::NotFound, "unused")
+            .unwrap(),
+        1
+    );
+}
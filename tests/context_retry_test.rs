@@ -0,0 +1,45 @@
+//! Tests for ResultExt::context_retry()
+
+use okerr::{Result, ResultExt, anyerr};
+
+fn flaky(attempt: usize) -> Result<&'static str> {
+    if attempt < 3 {
+        Err(anyerr!("service unavailable"))
+    } else {
+        Ok("done")
+    }
+}
+
+#[test]
+fn context_retry_format() {
+    let err = flaky(1).context_retry(1, 3).unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["attempt 1/3", "service unavailable"]);
+}
+
+#[test]
+fn context_retry_once_per_call() {
+    let mut errors = Vec::new();
+
+    for attempt in 1..=3 {
+        match flaky(attempt).context_retry(attempt, 3) {
+            Ok(value) => assert_eq!(value, "done"),
+            Err(err) => errors.push(format!("{err:#}")),
+        }
+    }
+
+    assert_eq!(
+        errors,
+        [
+            "attempt 1/3: service unavailable",
+            "attempt 2/3: service unavailable"
+        ]
+    );
+    assert!(errors.iter().all(|err| err.matches("attempt").count() == 1));
+}
+
+#[test]
+fn context_retry_ok_passes_through() {
+    assert_eq!(flaky(3).context_retry(3, 3).unwrap(), "done");
+}
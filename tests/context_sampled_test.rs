@@ -0,0 +1,61 @@
+//! Tests for ResultExt::context_sampled()
+
+use okerr::{Result, ResultExt, anyerr};
+
+fn failing() -> Result<()> {
+    Err(anyerr!("timeout"))
+}
+
+#[test]
+fn rate_one_always_attaches() {
+    for _ in 0..100 {
+        let err = failing()
+            .context_sampled(1.0, || "queue depth=42".to_string())
+            .unwrap_err();
+        assert_eq!(format!("{err:#}"), "queue depth=42: timeout");
+    }
+}
+
+#[test]
+fn rate_zero_never_attaches_nor_calls() {
+    for _ in 0..100 {
+        let err = failing()
+            .context_sampled(0.0, || panic!("must not be called"))
+            .unwrap_err();
+        assert_eq!(format!("{err:#}"), "timeout");
+    }
+}
+
+#[test]
+fn f_called_only_when_sampled() {
+    let mut calls = 0;
+    let mut attached = 0;
+
+    for _ in 0..2000 {
+        let err = failing()
+            .context_sampled(0.5, || {
+                calls += 1;
+                "details".to_string()
+            })
+            .unwrap_err();
+
+        if err.chain().count() == 2 {
+            attached += 1;
+        }
+    }
+
+    assert_eq!(calls, attached);
+    // Loose bounds, the sampling is random
+    assert!((600..1400).contains(&attached), "attached {attached} times");
+}
+
+#[test]
+fn ok_never_calls_f() {
+    let result: Result<u8> = Ok(1);
+    assert_eq!(
+        result
+            .context_sampled(1.0, || panic!("must not be called"))
+            .unwrap(),
+        1
+    );
+}
@@ -0,0 +1,74 @@
+//! Tests for enter_context() and ResultExt::in_scope()
+
+use okerr::{Result, ResultExt, anyerr, enter_context};
+
+fn failing() -> Result<()> {
+    Err(anyerr!("invalid email"))
+}
+
+#[test]
+fn nested_scopes_carry_full_stack() {
+    let import = enter_context("importing users");
+
+    let err = {
+        let _file = enter_context("users.csv");
+        let row = enter_context("row 42");
+        failing().in_scope(&row).unwrap_err()
+    };
+
+    assert_eq!(
+        format!("{err:#}"),
+        "importing users > users.csv > row 42: invalid email"
+    );
+
+    // Inner scopes ended
+    let err = failing().in_scope(&import).unwrap_err();
+    assert_eq!(format!("{err:#}"), "importing users: invalid email");
+}
+
+#[test]
+fn outer_scope_ignores_inner_labels() {
+    let outer = enter_context("outer");
+    let _inner = enter_context("inner");
+
+    let err = failing().in_scope(&outer).unwrap_err();
+    assert_eq!(err.to_string(), "outer");
+}
+
+#[test]
+fn scope_reused_after_sibling_ended() {
+    let outer = enter_context("sync");
+
+    for page in 1..=2 {
+        let scope = enter_context(format!("page {page}"));
+        let err = failing().in_scope(&scope).unwrap_err();
+        assert_eq!(err.to_string(), format!("sync > page {page}"));
+    }
+
+    drop(outer);
+}
+
+#[test]
+fn in_scope_keeps_ok() {
+    let scope = enter_context("ok");
+    let result: Result<u8> = Ok(1);
+    assert_eq!(result.in_scope(&scope).unwrap(), 1);
+}
+
+#[test]
+fn scopes_dropped_out_of_order() {
+    let outer = enter_context("outer");
+    let inner = enter_context("inner");
+
+    // Ending the outer scope first also ends the inner one
+    drop(outer);
+
+    let err = failing().in_scope(&inner).unwrap_err();
+    assert_eq!(format!("{err:#}"), "invalid email");
+
+    drop(inner);
+
+    let scope = enter_context("next");
+    let err = failing().in_scope(&scope).unwrap_err();
+    assert_eq!(format!("{err:#}"), "next: invalid email");
+}
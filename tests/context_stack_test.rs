@@ -0,0 +1,60 @@
+//! Tests for ContextStack and ResultExt::context_frames()
+
+use okerr::{ContextStack, Result, ResultExt, anyerr};
+
+fn fetch(stack: &mut ContextStack) -> Result<()> {
+    stack.push("fetch");
+    let result: Result<()> = Err(anyerr!("connection reset"));
+    let result = result.context_frames(stack);
+    stack.pop();
+    result
+}
+
+fn sync(stack: &mut ContextStack) -> Result<()> {
+    stack.push("sync");
+    let result = fetch(stack);
+    stack.pop();
+    result
+}
+
+#[test]
+fn context_frames_attaches_all_frames() {
+    let mut stack = ContextStack::new();
+    let err = sync(&mut stack).unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["sync > fetch", "connection reset"]);
+
+    // Frames have been popped
+    assert!(stack.frames().is_empty());
+}
+
+#[test]
+fn context_frames_empty_stack_adds_nothing() {
+    let stack = ContextStack::new();
+    let result: Result<()> = Err(anyerr!("fail"));
+
+    let err = result.context_frames(&stack).unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+    assert_eq!(err.to_string(), "fail");
+}
+
+#[test]
+fn context_frames_ok_passes_through() {
+    let mut stack = ContextStack::new();
+    stack.push("frame");
+
+    let result: Result<i32> = Ok(42);
+    assert_eq!(result.context_frames(&stack).unwrap(), 42);
+}
+
+#[test]
+fn context_stack_push_pop() {
+    let mut stack = ContextStack::new();
+    stack.push("a");
+    stack.push(String::from("b"));
+
+    assert_eq!(stack.frames(), ["a", "b"]);
+    assert_eq!(stack.pop().as_deref(), Some("b"));
+    assert_eq!(stack.frames(), ["a"]);
+}
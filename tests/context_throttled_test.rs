@@ -0,0 +1,79 @@
+//! Tests for ResultExt::context_throttled()
+
+use okerr::{Result, ResultExt, anyerr};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+fn failing() -> Result<()> {
+    Err(anyerr!("connection refused"))
+}
+
+#[test]
+fn context_only_first_time_per_key() {
+    let seen = Mutex::new(HashSet::new());
+
+    let first = failing()
+        .context_throttled("db", "database is down", &seen)
+        .unwrap_err();
+    let second = failing()
+        .context_throttled("db", "database is down", &seen)
+        .unwrap_err();
+
+    assert_eq!(format!("{first:#}"), "database is down: connection refused");
+    assert_eq!(format!("{second:#}"), "connection refused");
+}
+
+#[test]
+fn keys_are_independent() {
+    let seen = Mutex::new(HashSet::new());
+
+    let db = failing()
+        .context_throttled("db", "db down", &seen)
+        .unwrap_err();
+    let cache = failing()
+        .context_throttled("cache", "cache down", &seen)
+        .unwrap_err();
+
+    assert_eq!(db.to_string(), "db down");
+    assert_eq!(cache.to_string(), "cache down");
+    assert_eq!(seen.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn ok_does_not_consume_key() {
+    let seen = Mutex::new(HashSet::new());
+
+    let ok: Result<u8> = Ok(1);
+    assert_eq!(ok.context_throttled("db", "db down", &seen).unwrap(), 1);
+
+    let err = failing()
+        .context_throttled("db", "db down", &seen)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "db down");
+}
+
+#[test]
+fn shared_across_threads() {
+    let seen = Mutex::new(HashSet::new());
+
+    let with_context = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                scope.spawn(|| {
+                    let err = failing()
+                        .context_throttled("db", "db down", &seen)
+                        .unwrap_err();
+                    err.chain().count() == 2
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|added| *added)
+            .count()
+    });
+
+    assert_eq!(with_context, 1);
+}
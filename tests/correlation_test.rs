@@ -0,0 +1,58 @@
+//! Tests for ResultExt::correlation() and correlation_id()
+
+use okerr::{Context, Result, ResultExt, anyerr, correlation_id};
+use std::io;
+
+#[test]
+fn correlation_appears_in_display() {
+    let result: Result<()> = Err(anyerr!("upstream failed"));
+    let err = result.correlation("abc-123").unwrap_err();
+
+    assert_eq!(err.to_string(), "correlation_id=abc-123");
+    assert_eq!(
+        format!("{err:#}"),
+        "correlation_id=abc-123: upstream failed"
+    );
+}
+
+#[test]
+fn correlation_id_is_extractable() {
+    let result: Result<()> = Err(anyerr!("upstream failed"));
+    let err = result.correlation(42).unwrap_err();
+
+    assert_eq!(correlation_id(&err).as_deref(), Some("42"));
+}
+
+#[test]
+fn correlation_id_survives_context() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
+        .context("calling billing")
+        .correlation("req-7")
+        .context("handling request");
+
+    let err = result.unwrap_err();
+    assert_eq!(correlation_id(&err).as_deref(), Some("req-7"));
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(
+        chain,
+        [
+            "handling request",
+            "correlation_id=req-7",
+            "calling billing",
+            "timed out"
+        ]
+    );
+}
+
+#[test]
+fn correlation_id_none_without_marker() {
+    let err = anyerr!("plain").context("outer");
+    assert_eq!(correlation_id(&err), None);
+}
+
+#[test]
+fn correlation_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(result.correlation("id").unwrap(), 1);
+}
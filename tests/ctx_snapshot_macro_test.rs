@@ -0,0 +1,77 @@
+//! Tests for the ctx_snapshot! macro
+
+use okerr::{Result, anyerr, ctx_snapshot};
+use std::cell::Cell;
+use std::fmt;
+
+struct Connection {
+    host: String,
+    port: u16,
+    retries: u8,
+    secret: String,
+}
+
+impl Connection {
+    fn connect(&self) -> Result<()> {
+        ctx_snapshot!(Err(anyerr!("refused")), self, [host, port])
+    }
+}
+
+fn connection() -> Connection {
+    Connection {
+        host: "db.local".to_string(),
+        port: 5432,
+        retries: 3,
+        secret: "hunter2".to_string(),
+    }
+}
+
+#[test]
+fn only_listed_fields_appear() {
+    let err = connection().connect().unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "host=\"db.local\" port=5432: refused");
+    assert!(!format!("{err:#}").contains("hunter2"));
+    assert!(!format!("{err:#}").contains("retries"));
+}
+
+#[test]
+fn snapshot_of_local_value() {
+    let conn = connection();
+    let result: Result<()> = ctx_snapshot!(Err(anyerr!("timeout")), conn, [retries, secret,]);
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "retries=3 secret=\"hunter2\""
+    );
+    assert_eq!(conn.port, 5432);
+}
+
+#[test]
+fn snapshot_is_lazy() {
+    struct Counted<'a>(&'a Cell<u32>);
+
+    impl fmt::Debug for Counted<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.write_str("counted")
+        }
+    }
+
+    struct State<'a> {
+        value: Counted<'a>,
+    }
+
+    let calls = Cell::new(0);
+    let state = State {
+        value: Counted(&calls),
+    };
+
+    let ok: Result<u8> = ctx_snapshot!(Ok::<u8, std::io::Error>(1), state, [value]);
+    assert_eq!(ok.unwrap(), 1);
+    assert_eq!(calls.get(), 0);
+
+    let err: Result<u8> = ctx_snapshot!(Err(anyerr!("boom")), state, [value]);
+    assert_eq!(err.unwrap_err().to_string(), "value=counted");
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,61 @@
+//! Tests for limits::DepthGuard and ResultExt::context_guarded()
+
+use okerr::{Result, ResultExt, anyerr, limits::DepthGuard};
+
+fn recurse(depth: usize, guard: &DepthGuard) -> Result<()> {
+    if depth == 0 {
+        return Err(anyerr!("bottom reached"));
+    }
+
+    recurse(depth - 1, guard).context_guarded(guard, format!("depth {depth}"))
+}
+
+#[test]
+fn context_guarded_stops_at_max_depth() {
+    let guard = DepthGuard::new(3);
+    let err = recurse(10, &guard).unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["depth 2", "depth 1", "bottom reached"]);
+}
+
+#[test]
+fn context_guarded_counts_suppressed() {
+    let guard = DepthGuard::new(3);
+    let _ = recurse(10, &guard);
+
+    // 2 levels added, 8 skipped
+    assert_eq!(guard.suppressed(), 8);
+    assert_eq!(guard.max_depth(), 3);
+}
+
+#[test]
+fn context_guarded_under_limit() {
+    let guard = DepthGuard::new(10);
+    let err = recurse(3, &guard).unwrap_err();
+
+    assert_eq!(err.chain().count(), 4);
+    assert_eq!(guard.suppressed(), 0);
+}
+
+#[test]
+fn context_guarded_ok_passes_through() {
+    let guard = DepthGuard::new(0);
+    let result: Result<i32> = Ok(1);
+
+    assert_eq!(result.context_guarded(&guard, "unused").unwrap(), 1);
+    assert_eq!(guard.suppressed(), 0);
+}
+
+#[test]
+fn markers_do_not_count_as_levels() {
+    let guard = DepthGuard::new(2);
+    let result: Result<()> = Err(std::io::Error::other("disk full").into());
+    let err = result
+        .mark_user()
+        .context_guarded(&guard, "saving report")
+        .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "saving report: disk full");
+    assert_eq!(guard.suppressed(), 0);
+}
@@ -0,0 +1,74 @@
+//! Tests for derive::prelude
+
+use okerr::derive::prelude::*;
+use std::io;
+
+#[derive(Error, Debug)]
+enum StorageError {
+    #[error("key not found: {0}")]
+    NotFound(String),
+    #[error("storage unavailable")]
+    Unavailable(#[from] io::Error),
+    #[error("invalid value for {key}: {reason}")]
+    Invalid { key: String, reason: String },
+}
+
+fn get(key: &str) -> Result<String> {
+    match key {
+        "missing" => err!(StorageError::NotFound(key.to_string())),
+        "invalid" => Err(StorageError::Invalid {
+            key: key.to_string(),
+            reason: "not utf-8".to_string(),
+        })
+        .context("reading value"),
+        "down" => Err(StorageError::from(io::Error::other("disk offline")))?,
+        _ => Ok(format!("value of {key}")),
+    }
+}
+
+#[test]
+fn derive_prelude_defines_error_enum() {
+    assert_eq!(get("name").unwrap(), "value of name");
+    assert_eq!(
+        get("missing").unwrap_err().to_string(),
+        "key not found: missing"
+    );
+}
+
+#[test]
+fn derive_prelude_error_type_and_context() {
+    let err: Error = get("invalid").unwrap_err();
+
+    assert_eq!(err.to_string(), "reading value");
+    assert!(matches!(
+        err.downcast_ref::<StorageError>(),
+        Some(StorageError::Invalid { .. })
+    ));
+}
+
+#[test]
+fn derive_prelude_from_source() {
+    let err = get("down").unwrap_err();
+
+    assert_eq!(err.to_string(), "storage unavailable");
+    assert_eq!(err.root_cause().to_string(), "disk offline");
+}
+
+#[test]
+fn derive_prelude_brings_macros_and_traits() {
+    fn first(values: &[i32]) -> Result<i32> {
+        ensure!(!values.is_empty(), StorageError::NotFound("first".into()));
+        let value = must!(values.first(), "unreachable");
+        values.get(1).ok_or_fail("no second value")?;
+        if *value < 0 {
+            fail!("negative value");
+        }
+        Ok(*value)
+    }
+
+    assert_eq!(first(&[1, 2]).unwrap(), 1);
+    assert_eq!(first(&[]).unwrap_err().to_string(), "key not found: first");
+    assert_eq!(first(&[1]).unwrap_err().to_string(), "no second value");
+    assert_eq!(first(&[-1, 0]).unwrap_err().to_string(), "negative value");
+    assert!(anyerr!("x").to_string().eq("x"));
+}
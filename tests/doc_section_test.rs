@@ -0,0 +1,55 @@
+//! Tests for ResultExt::doc_section(), doc_section() and format_with_doc_section()
+
+use okerr::{Context, Result, ResultExt, anyerr, doc_section, format_with_doc_section};
+
+fn failing() -> Result<()> {
+    Err(anyerr!("unknown flag --colour"))
+}
+
+#[test]
+fn section_is_extractable_after_context() {
+    let err = failing()
+        .doc_section("Command-line options")
+        .context("parsing arguments")
+        .unwrap_err();
+
+    assert_eq!(doc_section(&err), Some("Command-line options"));
+}
+
+#[test]
+fn section_excluded_from_display() {
+    let err = failing().doc_section("Command-line options").unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "unknown flag --colour");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn doc_aware_formatter_includes_section() {
+    let err = failing()
+        .doc_section("Command-line options")
+        .context("parsing arguments")
+        .unwrap_err();
+
+    assert_eq!(
+        format_with_doc_section(&err),
+        "parsing arguments: unknown flag --colour\nSee the 'Command-line options' section of the manual."
+    );
+}
+
+#[test]
+fn last_section_wins() {
+    let err = failing()
+        .doc_section("Basics")
+        .doc_section("Flags")
+        .unwrap_err();
+    assert_eq!(doc_section(&err), Some("Flags"));
+}
+
+#[test]
+fn no_section() {
+    let err = failing().unwrap_err();
+
+    assert_eq!(doc_section(&err), None);
+    assert_eq!(format_with_doc_section(&err), "unknown flag --colour");
+}
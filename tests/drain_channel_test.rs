@@ -0,0 +1,67 @@
+//! Tests for sync::drain_channel()
+
+use okerr::{Result, anyerr, sync::drain_channel};
+use std::sync::mpsc;
+use std::thread;
+
+#[test]
+fn drain_channel_partitions_in_order() {
+    let (tx, rx) = mpsc::channel();
+
+    for i in 0..6 {
+        let item: Result<i32> = if i % 3 == 0 {
+            Err(anyerr!("item {i} failed"))
+        } else {
+            Ok(i)
+        };
+        tx.send(item).unwrap();
+    }
+    drop(tx);
+
+    let (values, errors) = drain_channel(rx);
+
+    assert_eq!(values, [1, 2, 4, 5]);
+    let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+    assert_eq!(messages, ["item 0 failed", "item 3 failed"]);
+}
+
+#[test]
+fn drain_channel_from_workers() {
+    let (tx, rx) = mpsc::channel();
+
+    let workers: Vec<_> = (0..4)
+        .map(|worker| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let result = if worker == 2 {
+                    Err(anyerr!("worker {worker} crashed"))
+                } else {
+                    Ok(worker * 10)
+                };
+                tx.send(result).unwrap();
+            })
+        })
+        .collect();
+    drop(tx);
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let (mut values, errors) = drain_channel(rx);
+    values.sort();
+
+    assert_eq!(values, [0, 10, 30]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "worker 2 crashed");
+}
+
+#[test]
+fn drain_channel_empty() {
+    let (tx, rx) = mpsc::channel::<Result<i32>>();
+    drop(tx);
+
+    let (values, errors) = drain_channel(rx);
+    assert!(values.is_empty());
+    assert!(errors.is_empty());
+}
@@ -0,0 +1,38 @@
+//! Tests for the ensure_invariant! macro
+
+use okerr::{Result, ensure_invariant};
+
+fn withdraw(balance: u64, amount: u64) -> Result<u64> {
+    ensure_invariant!(amount <= balance, "overdraft: {amount} > {balance}");
+    Ok(balance - amount)
+}
+
+#[test]
+fn invariant_holds() {
+    assert_eq!(withdraw(100, 30).unwrap(), 70);
+    assert_eq!(withdraw(5, 5).unwrap(), 0);
+}
+
+/// Panics in debug builds, returns the error in release builds
+/// (`cargo test --release`).
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "overdraft: 50 > 10"))]
+fn invariant_violated() {
+    let err = withdraw(10, 50).unwrap_err();
+    assert_eq!(err.to_string(), "overdraft: 50 > 10");
+}
+
+fn reserve(stock: u32, quantity: u32) -> Result<u32> {
+    let message = format!("out of stock: {quantity} > {stock}");
+    ensure_invariant!(quantity <= stock, message);
+    Ok(stock - quantity)
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "out of stock: 3 > 2"))]
+fn invariant_violated_with_string_message() {
+    assert_eq!(reserve(2, 1).unwrap(), 1);
+
+    let err = reserve(2, 3).unwrap_err();
+    assert_eq!(err.to_string(), "out of stock: 3 > 2");
+}
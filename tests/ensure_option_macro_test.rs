@@ -0,0 +1,73 @@
+//! Tests for ensure_some! and ensure_none! macros
+
+use okerr::{Result, ensure_none, ensure_some};
+
+#[test]
+fn ensure_some_binds_value() {
+    fn operation(value: Option<&str>) -> Result<String> {
+        let value = ensure_some!(value, "must be present");
+        Ok(value.to_uppercase())
+    }
+
+    assert_eq!(operation(Some("abc")).unwrap(), "ABC");
+}
+
+#[test]
+fn ensure_some_bails_on_none() {
+    fn operation(value: Option<&str>) -> Result<String> {
+        let value = ensure_some!(value, "must be present");
+        Ok(value.to_uppercase())
+    }
+
+    let result = operation(None);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().to_string(), "must be present");
+}
+
+#[test]
+fn ensure_some_with_format() {
+    fn operation(name: &str) -> Result<i32> {
+        let value = ensure_some!(None::<i32>, "'{}' must be present", name);
+        Ok(value)
+    }
+
+    assert_eq!(
+        operation("id").unwrap_err().to_string(),
+        "'id' must be present"
+    );
+}
+
+#[test]
+fn ensure_none_continues_on_none() {
+    fn operation(value: Option<i32>) -> Result<&'static str> {
+        ensure_none!(value, "must be absent");
+        Ok("continued")
+    }
+
+    assert_eq!(operation(None).unwrap(), "continued");
+}
+
+#[test]
+fn ensure_none_bails_on_some() {
+    fn operation(value: Option<i32>) -> Result<&'static str> {
+        ensure_none!(value, "must be absent, got {:?}", value);
+        Ok("continued")
+    }
+
+    let result = operation(Some(7));
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "must be absent, got Some(7)"
+    );
+}
+
+#[test]
+fn ensure_none_does_not_consume_option() {
+    fn operation(value: Option<String>) -> Result<Option<String>> {
+        ensure_none!(value, "must be absent");
+        Ok(value)
+    }
+
+    assert_eq!(operation(None).unwrap(), None);
+}
@@ -0,0 +1,75 @@
+//! Tests for env::require_var() and env::require_var_parsed()
+
+use okerr::env::{require_var, require_var_parsed};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Serializes the environment accesses of the tests (which run in parallel):
+/// `set_var` must not run while another thread reads the environment.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_env() -> MutexGuard<'static, ()> {
+    ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn require_var_set() {
+    let _env = lock_env();
+
+    // SAFETY: the environment lock is held, no other thread accesses the environment.
+    unsafe { std::env::set_var("OKERR_TEST_ENV_SET", "hello") };
+
+    assert_eq!(require_var("OKERR_TEST_ENV_SET").unwrap(), "hello");
+}
+
+#[test]
+fn require_var_unset() {
+    let _env = lock_env();
+
+    let err = require_var("OKERR_TEST_ENV_UNSET").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "environment variable OKERR_TEST_ENV_UNSET is not set"
+    );
+}
+
+#[test]
+fn require_var_parsed_ok() {
+    let _env = lock_env();
+
+    // SAFETY: the environment lock is held, no other thread accesses the environment.
+    unsafe { std::env::set_var("OKERR_TEST_ENV_PORT", "8080") };
+
+    let port: u16 = require_var_parsed("OKERR_TEST_ENV_PORT").unwrap();
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn require_var_parsed_invalid() {
+    let _env = lock_env();
+
+    // SAFETY: the environment lock is held, no other thread accesses the environment.
+    unsafe { std::env::set_var("OKERR_TEST_ENV_BAD_PORT", "eighty") };
+
+    let err = require_var_parsed::<u16>("OKERR_TEST_ENV_BAD_PORT").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"invalid value for environment variable OKERR_TEST_ENV_BAD_PORT: "eighty""#
+    );
+    assert_eq!(
+        err.root_cause().to_string(),
+        "invalid digit found in string"
+    );
+}
+
+#[test]
+fn require_var_parsed_unset() {
+    let _env = lock_env();
+
+    let err = require_var_parsed::<u16>("OKERR_TEST_ENV_PARSED_UNSET").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "environment variable OKERR_TEST_ENV_PARSED_UNSET is not set"
+    );
+}
@@ -0,0 +1,68 @@
+//! Tests for err_set! macro and ErrorSet
+
+use okerr::{ErrorSet, Result, anyerr, err_set};
+
+#[test]
+fn err_set_drops_duplicates() {
+    let set = err_set!["a", "b", "a"];
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.messages().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[test]
+fn err_set_renders_sorted() {
+    let set = err_set![
+        "zip code is invalid",
+        "age is negative",
+        "name is empty",
+        "age is negative",
+    ];
+
+    assert_eq!(
+        set.to_string(),
+        "3 errors occurred:\n  \
+         - age is negative\n  \
+         - name is empty\n  \
+         - zip code is invalid"
+    );
+}
+
+#[test]
+fn err_set_empty() {
+    let set = err_set![];
+
+    assert!(set.is_empty());
+    assert_eq!(set.to_string(), "0 errors occurred:");
+}
+
+#[test]
+fn err_set_with_formatted_messages() {
+    let field = "email";
+    let set = err_set![format!("{field} is required"), "email is required"];
+
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn error_set_insert_reports_duplicates() {
+    let mut set = ErrorSet::new();
+
+    assert!(set.insert("first"));
+    assert!(!set.insert("first"));
+    assert!(set.insert_error(&anyerr!("root").context("outer")));
+    assert!(!set.insert("outer: root"));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn error_set_as_error() {
+    fn validate() -> Result<()> {
+        let set: ErrorSet = ["b", "a", "b"].into_iter().collect();
+        Err(set)?
+    }
+
+    let err = validate().unwrap_err();
+    assert_eq!(err.downcast_ref::<ErrorSet>().unwrap().len(), 2);
+    assert_eq!(err.to_string(), "2 errors occurred:\n  - a\n  - b");
+}
@@ -0,0 +1,71 @@
+//! Tests for ErrorSet::into_result_threshold()
+
+#[cfg(feature = "tracing")]
+mod common;
+
+use okerr::ErrorSet;
+
+#[test]
+fn ok_under_threshold() {
+    let set: ErrorSet = ["row 3: bad date"].into_iter().collect();
+    assert!(set.into_result_threshold(2).is_ok());
+}
+
+#[test]
+fn ok_at_threshold() {
+    let set: ErrorSet = ["row 3: bad date", "row 9: bad amount"]
+        .into_iter()
+        .collect();
+    assert!(set.into_result_threshold(2).is_ok());
+}
+
+#[test]
+fn ok_when_empty() {
+    assert!(ErrorSet::new().into_result_threshold(0).is_ok());
+}
+
+#[test]
+fn err_over_threshold() {
+    let set: ErrorSet = ["row 9: bad amount", "row 3: bad date", "row 9: bad amount"]
+        .into_iter()
+        .collect();
+
+    let err = set.clone().into_result_threshold(1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2 errors occurred:\n  - row 3: bad date\n  - row 9: bad amount"
+    );
+    assert_eq!(err.downcast_ref::<ErrorSet>(), Some(&set));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tolerated_errors_are_logged_as_warnings() {
+    use common::Capture;
+    use tracing::Level;
+
+    let capture = Capture::default();
+    let set: ErrorSet = ["row 3: bad date", "row 9: bad amount"]
+        .into_iter()
+        .collect();
+
+    assert!(capture.run(|| set.into_result_threshold(5)).is_ok());
+
+    let events = capture.events();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.level == Level::WARN));
+    assert_eq!(events[0].message, "tolerated error: row 3: bad date");
+    assert_eq!(events[1].message, "tolerated error: row 9: bad amount");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn nothing_logged_over_threshold() {
+    use common::Capture;
+
+    let capture = Capture::default();
+    let set: ErrorSet = ["a", "b"].into_iter().collect();
+
+    assert!(capture.run(|| set.into_result_threshold(1)).is_err());
+    assert!(capture.events().is_empty());
+}
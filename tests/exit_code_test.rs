@@ -0,0 +1,53 @@
+//! Tests for to_exit_code() and main_result()
+
+use okerr::{Context, Result, ResultExt, anyerr, main_result, to_exit_code};
+use std::process::ExitCode;
+
+#[test]
+fn untagged_error_maps_to_failure() {
+    assert_eq!(to_exit_code(&anyerr!("boom")), ExitCode::FAILURE);
+}
+
+#[test]
+fn tagged_error_maps_to_its_code() {
+    let result: Result<()> = Err(anyerr!("config missing"));
+    let err = result.with_exit_code(78).context("starting").unwrap_err();
+
+    assert_eq!(to_exit_code(&err), ExitCode::from(78));
+    assert_eq!(format!("{err:#}"), "starting: config missing");
+}
+
+#[test]
+fn last_code_wins() {
+    let result: Result<()> = Err(anyerr!("usage"));
+    let err = result.with_exit_code(64).with_exit_code(2).unwrap_err();
+
+    assert_eq!(to_exit_code(&err), ExitCode::from(2));
+}
+
+#[test]
+fn main_result_success() {
+    assert_eq!(main_result(|| Ok(())), ExitCode::SUCCESS);
+}
+
+#[test]
+fn main_result_maps_error_code() {
+    let code = main_result(|| {
+        Err(anyerr!("permission denied"))
+            .with_exit_code(77)
+            .context("opening database")
+    });
+    assert_eq!(code, ExitCode::from(77));
+
+    assert_eq!(main_result(|| Err(anyerr!("boom"))), ExitCode::FAILURE);
+}
+
+#[test]
+fn zero_code_is_still_a_failure() {
+    let result: Result<()> = Err(anyerr!("boom"));
+    let err = result.with_exit_code(0).unwrap_err();
+    assert_eq!(to_exit_code(&err), ExitCode::FAILURE);
+
+    let code = main_result(|| Err(anyerr!("boom")).with_exit_code(0));
+    assert_eq!(code, ExitCode::FAILURE);
+}
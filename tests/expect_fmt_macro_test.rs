@@ -0,0 +1,38 @@
+//! Tests for expect_fmt! macro
+
+use okerr::{Context, Result, anyerr, expect_fmt};
+
+fn failing() -> Result<i32> {
+    Err(anyerr!("connection refused")).context("fetching user 42")
+}
+
+#[test]
+fn expect_fmt_unwraps_ok() {
+    let result: Result<i32> = Ok(42);
+    let value = expect_fmt!(result, "could not {}", "compute");
+
+    assert_eq!(value, 42);
+}
+
+#[test]
+#[should_panic(expected = "could not sync profile: fetching user 42: connection refused")]
+fn expect_fmt_panics_with_prefix_and_chain() {
+    let action = "sync";
+    expect_fmt!(failing(), "could not {} {}", action, "profile");
+}
+
+#[test]
+#[should_panic(expected = "connection refused")]
+fn expect_fmt_panic_contains_source() {
+    expect_fmt!(failing(), "could not {}", "fetch");
+}
+
+#[test]
+#[should_panic(expected = "static message: bad input")]
+fn expect_fmt_without_args() {
+    let result: std::result::Result<(), std::fmt::Error> = Ok(());
+    expect_fmt!(result, "unused");
+
+    let result: Result<()> = Err(anyerr!("bad input"));
+    expect_fmt!(result, "static message");
+}
@@ -0,0 +1,97 @@
+//! Tests for ResultExt::mark_user(), ResultExt::mark_system() and fault classification
+
+use okerr::{
+    Context, FaultClass, Result, ResultExt, anyerr, fault_class, is_system_error, is_user_error,
+};
+use std::io;
+
+fn chain_of(err: &okerr::Error) -> Vec<String> {
+    err.chain().map(|cause| cause.to_string()).collect()
+}
+
+#[test]
+fn mark_user_classifies() {
+    let result: Result<()> = Err(anyerr!("invalid email"));
+    let err = result.mark_user().unwrap_err();
+
+    assert!(is_user_error(&err));
+    assert!(!is_system_error(&err));
+    assert_eq!(fault_class(&err), FaultClass::User);
+}
+
+#[test]
+fn mark_system_classifies() {
+    let result: Result<()> = Err(anyerr!("disk full"));
+    let err = result.mark_system().unwrap_err();
+
+    assert!(is_system_error(&err));
+    assert!(!is_user_error(&err));
+}
+
+#[test]
+fn unmarked_defaults_to_system() {
+    let err = anyerr!("unknown").context("outer");
+
+    assert!(is_system_error(&err));
+    assert_eq!(fault_class(&err), FaultClass::System);
+}
+
+#[test]
+fn classification_survives_context() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::InvalidInput, "bad json"))
+        .context("parsing body")
+        .mark_user()
+        .context("handling request")
+        .context("serving");
+
+    let err = result.unwrap_err();
+    assert!(is_user_error(&err));
+}
+
+#[test]
+fn marking_keeps_message_and_chain() {
+    let result: Result<()> = Err(anyerr!("root")).context("middle");
+    let err = result.mark_user().context("outer").unwrap_err();
+
+    assert_eq!(chain_of(&err), ["outer", "middle", "root"]);
+    assert_eq!(format!("{err:#}"), "outer: middle: root");
+}
+
+#[test]
+fn last_mark_wins() {
+    let result: Result<()> = Err(anyerr!("fail"));
+    let err = result
+        .mark_user()
+        .context("retrying")
+        .mark_system()
+        .unwrap_err();
+
+    assert!(is_system_error(&err));
+    assert_eq!(chain_of(&err), ["retrying", "fail"]);
+}
+
+#[test]
+fn mark_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(result.mark_user().unwrap(), 1);
+}
+
+#[test]
+fn marked_io_error_keeps_message_and_kind() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "config.toml").into());
+    let err = result.mark_user().unwrap_err();
+
+    assert!(is_user_error(&err));
+    assert_eq!(format!("{err:#}"), "config.toml");
+    assert_eq!(err.chain().count(), 1);
+    assert!(format!("{err:#?}").contains("NotFound"));
+    assert_eq!(
+        okerr::category::category(&err),
+        Some(okerr::category::Category::NotFound)
+    );
+
+    let err = Err::<(), _>(err)
+        .context_on_io_kind(io::ErrorKind::NotFound, "missing config")
+        .unwrap_err();
+    assert_eq!(format!("{err:#}"), "missing config: config.toml");
+}
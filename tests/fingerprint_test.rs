@@ -0,0 +1,70 @@
+//! Tests for fingerprint() and fingerprint_normalized()
+
+use okerr::{Context, Result, anyerr, anyerr_chain, fingerprint, fingerprint_normalized};
+
+fn request_failed(user: u32, port: u16) -> okerr::Error {
+    let result: Result<()> = Err(anyerr!("connection refused on port {port}"))
+        .with_context(|| format!("loading user {user}"));
+
+    result.unwrap_err()
+}
+
+#[test]
+fn fingerprint_identical_chains_match() {
+    assert_eq!(
+        fingerprint(&request_failed(1, 80)),
+        fingerprint(&request_failed(1, 80))
+    );
+}
+
+#[test]
+fn fingerprint_different_chains_differ() {
+    assert_ne!(
+        fingerprint(&request_failed(1, 80)),
+        fingerprint(&request_failed(2, 80))
+    );
+    assert_ne!(fingerprint(&anyerr!("a")), fingerprint(&anyerr!("b")));
+}
+
+#[test]
+fn fingerprint_depends_on_levels() {
+    let split = anyerr_chain(["bc".to_string(), "a".to_string()]);
+    let other_split = anyerr_chain(["c".to_string(), "ab".to_string()]);
+
+    assert_ne!(fingerprint(&split), fingerprint(&other_split));
+}
+
+#[test]
+fn fingerprint_is_stable() {
+    // FNV-1a of "oops" followed by the separator
+    assert_eq!(fingerprint(&anyerr!("oops")), 0x3ae5_629c_9469_f5c3);
+}
+
+#[test]
+fn fingerprint_normalized_ignores_numbers() {
+    assert_eq!(
+        fingerprint_normalized(&request_failed(1, 80)),
+        fingerprint_normalized(&request_failed(1234, 8080))
+    );
+}
+
+#[test]
+fn fingerprint_normalized_keeps_text() {
+    assert_ne!(
+        fingerprint_normalized(&anyerr!("user 1 not found")),
+        fingerprint_normalized(&anyerr!("group 1 not found"))
+    );
+}
+
+#[test]
+fn fingerprint_normalized_replaces_digit_runs() {
+    // A run of digits is one "N", whatever its length
+    assert_eq!(
+        fingerprint_normalized(&anyerr!("took 5ms")),
+        fingerprint_normalized(&anyerr!("took 12345ms"))
+    );
+    assert_eq!(
+        fingerprint_normalized(&anyerr!("v1.2")),
+        fingerprint(&anyerr!("vN.N"))
+    );
+}
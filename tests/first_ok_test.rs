@@ -0,0 +1,46 @@
+//! Tests for first_ok()
+
+use okerr::{MultiError, Result, anyerr, first_ok};
+use std::cell::Cell;
+
+type Attempt<'a> = Box<dyn FnOnce() -> Result<u32> + 'a>;
+
+#[test]
+fn short_circuits_on_first_success() {
+    let runs = Cell::new(0);
+
+    let attempts = (0..5u32).map(|i| {
+        let runs = &runs;
+        Box::new(move || {
+            runs.set(runs.get() + 1);
+            if i < 2 {
+                return Err(anyerr!("attempt {i} failed"));
+            }
+            Ok(i * 10)
+        }) as Attempt<'_>
+    });
+
+    assert_eq!(first_ok(attempts).unwrap(), 20);
+    assert_eq!(runs.get(), 3);
+}
+
+#[test]
+fn aggregates_when_all_fail() {
+    let attempts: Vec<Attempt<'static>> = vec![
+        Box::new(|| Err(anyerr!("cache miss"))),
+        Box::new(|| Err(anyerr!("db timeout"))),
+    ];
+
+    let err = first_ok(attempts).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2 errors occurred:\n  - cache miss\n  - db timeout"
+    );
+    assert_eq!(err.downcast_ref::<MultiError>().unwrap().len(), 2);
+}
+
+#[test]
+fn no_attempts_fails() {
+    let err = first_ok(Vec::<Attempt<'static>>::new()).unwrap_err();
+    assert!(err.downcast_ref::<MultiError>().unwrap().is_empty());
+}
@@ -0,0 +1,40 @@
+//! Tests for FlattenDisplay
+
+use okerr::{Context, FlattenDisplay, Result, anyerr, anyerr_chain};
+use std::io;
+
+#[test]
+fn flatten_display_multi_level() {
+    let err = anyerr_chain(["root", "middle", "outer"].map(String::from));
+
+    assert_eq!(FlattenDisplay(&err).to_string(), "outer: middle: root");
+}
+
+#[test]
+fn flatten_display_with_std_sources() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "config.toml"))
+        .context("reading config")
+        .context("starting");
+
+    let err = result.unwrap_err();
+    assert_eq!(
+        format!("{}", FlattenDisplay(&err)),
+        "starting: reading config: config.toml"
+    );
+    // Same as anyhow's alternate Display
+    assert_eq!(FlattenDisplay(&err).to_string(), format!("{err:#}"));
+}
+
+#[test]
+fn flatten_display_single_level() {
+    let err = anyerr!("alone");
+    assert_eq!(FlattenDisplay(&err).to_string(), "alone");
+}
+
+#[test]
+fn flatten_display_in_log_line() {
+    let err = anyerr!("timeout").context("calling billing");
+    let line = format!("level=error msg=\"{}\"", FlattenDisplay(&err));
+
+    assert_eq!(line, "level=error msg=\"calling billing: timeout\"");
+}
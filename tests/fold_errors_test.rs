@@ -0,0 +1,57 @@
+//! Tests for fold_errors()
+
+use okerr::{Context, Result, anyerr, fold_errors};
+use std::io;
+
+#[test]
+fn fold_errors_returns_successes() {
+    let results: Vec<Result<i32>> = vec![Ok(1), Ok(2), Ok(3)];
+    assert_eq!(fold_errors(results).unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn fold_errors_empty() {
+    let results: Vec<Result<i32>> = Vec::new();
+    assert!(fold_errors(results).unwrap().is_empty());
+}
+
+#[test]
+fn fold_errors_chains_every_failure() {
+    let results: Vec<Result<i32>> = vec![
+        Err(anyerr!("a failed")),
+        Ok(1),
+        Err(anyerr!("b failed")),
+        Ok(2),
+        Err(anyerr!("c failed")),
+    ];
+
+    let err = fold_errors(results).unwrap_err();
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["c failed", "b failed", "a failed"]);
+}
+
+#[test]
+fn fold_errors_flattens_nested_chains() {
+    let results: Vec<Result<()>> = vec![
+        Err(anyerr!("timeout")),
+        Err(anyerr!("refused")).context("connecting"),
+    ];
+
+    let err = fold_errors(results).unwrap_err();
+    assert_eq!(format!("{err:#}"), "connecting: refused: timeout");
+    assert_eq!(err.chain().count(), 2);
+}
+
+#[test]
+fn fold_errors_keeps_first_error_as_root() {
+    let results: Vec<Result<()>> = vec![
+        Err(io::Error::new(io::ErrorKind::NotFound, "missing").into()),
+        Err(anyerr!("other")),
+    ];
+
+    let err = fold_errors(results).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<io::Error>().map(io::Error::kind),
+        Some(io::ErrorKind::NotFound)
+    );
+}
@@ -0,0 +1,44 @@
+//! Tests for format_chain_table()
+
+use okerr::{anyerr, anyerr_chain, format_chain_table};
+
+#[test]
+fn format_chain_table_one_row_per_level() {
+    let err = anyerr_chain(["root", "middle", "outer"].map(String::from));
+    let table = format_chain_table(&err);
+
+    assert_eq!(table, "0 | outer\n1 | middle\n2 | root");
+}
+
+#[test]
+fn format_chain_table_increments_indices() {
+    let err = anyerr_chain((0..5).map(|i| format!("message {i}")));
+    let table = format_chain_table(&err);
+
+    for (i, row) in table.lines().enumerate() {
+        assert!(row.starts_with(&format!("{i} | ")));
+    }
+
+    assert_eq!(table.lines().count(), 5);
+}
+
+#[test]
+fn format_chain_table_pads_to_widest_level() {
+    let err = anyerr_chain((0..12).map(|i| format!("message {i}")));
+    let table = format_chain_table(&err);
+    let rows: Vec<&str> = table.lines().collect();
+
+    assert_eq!(rows.len(), 12);
+    assert_eq!(rows[0], " 0 | message 11");
+    assert_eq!(rows[9], " 9 | message 2");
+    assert_eq!(rows[10], "10 | message 1");
+    assert_eq!(rows[11], "11 | message 0");
+
+    // Messages column is aligned
+    assert!(rows.iter().all(|row| row.find('|') == Some(3)));
+}
+
+#[test]
+fn format_chain_table_single_error() {
+    assert_eq!(format_chain_table(&anyerr!("alone")), "0 | alone");
+}
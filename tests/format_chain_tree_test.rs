@@ -0,0 +1,29 @@
+//! Tests for format_chain_tree()
+
+use okerr::{anyerr, anyerr_chain, format_chain_tree};
+
+#[test]
+fn tree_connectors_and_indentation() {
+    let err = anyerr_chain(["root", "cause", "middle", "outer"].map(String::from));
+
+    assert_eq!(
+        format_chain_tree(&err),
+        "outer\n└─ middle\n   └─ cause\n      └─ root"
+    );
+}
+
+#[test]
+fn branches_equal_depth_minus_one() {
+    for depth in 1..=5 {
+        let err = anyerr_chain((0..depth).map(|i| format!("level {i}")));
+        let tree = format_chain_tree(&err);
+
+        assert_eq!(tree.matches("└─").count(), depth - 1);
+        assert_eq!(tree.lines().count(), depth);
+    }
+}
+
+#[test]
+fn single_level_has_no_branch() {
+    assert_eq!(format_chain_tree(&anyerr!("alone")), "alone");
+}
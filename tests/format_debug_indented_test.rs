@@ -0,0 +1,47 @@
+//! Tests for format_debug_indented()
+
+use okerr::{anyerr, anyerr_chain, format_debug_indented};
+
+#[test]
+fn three_levels_numbered_and_indented() {
+    let err = anyerr_chain(["root", "middle", "outer"].map(String::from));
+
+    assert_eq!(
+        format_debug_indented(&err, 6),
+        "outer\n\nCaused by:\n      0: middle\n      1: root"
+    );
+}
+
+#[test]
+fn matches_anyhow_debug_with_four_spaces() {
+    let err = anyerr!("root").context("middle").context("outer");
+    let debug = format!("{err:?}");
+    // The backtrace (if enabled) is not rendered
+    let debug = debug.split("\n\nStack backtrace:").next().unwrap();
+
+    assert_eq!(format_debug_indented(&err, 4), debug.trim_end());
+}
+
+#[test]
+fn zero_indent() {
+    let err = anyerr!("root").context("outer");
+    assert_eq!(
+        format_debug_indented(&err, 0),
+        "outer\n\nCaused by:\n0: root"
+    );
+}
+
+#[test]
+fn single_level_has_no_causes() {
+    assert_eq!(format_debug_indented(&anyerr!("alone"), 4), "alone");
+}
+
+#[test]
+fn multiline_cause_is_aligned() {
+    let err = anyerr!("line one\nline two").context("outer");
+
+    assert_eq!(
+        format_debug_indented(&err, 2),
+        "outer\n\nCaused by:\n  0: line one\n     line two"
+    );
+}
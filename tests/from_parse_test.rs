@@ -0,0 +1,49 @@
+//! Tests for convert::from_parse()
+
+use okerr::convert::from_parse;
+use std::net::IpAddr;
+
+#[test]
+fn from_parse_ok() {
+    assert_eq!(from_parse("42".parse::<u8>(), "42").unwrap(), 42);
+}
+
+#[test]
+fn from_parse_shows_input() {
+    let input = "10.0.0.300";
+    let err = from_parse(input.parse::<IpAddr>(), input).unwrap_err();
+
+    assert_eq!(err.to_string(), "failed to parse \"10.0.0.300\"");
+    assert_eq!(err.root_cause().to_string(), "invalid IP address syntax");
+    assert!(err.downcast_ref::<std::net::AddrParseError>().is_some());
+}
+
+#[test]
+fn from_parse_truncates_long_input() {
+    let input = "x".repeat(100);
+    let err = from_parse(input.parse::<f64>(), &input).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!("failed to parse \"{}...\"", "x".repeat(40))
+    );
+}
+
+#[test]
+fn from_parse_truncates_on_char_boundary() {
+    let input = "é".repeat(50);
+    let err = from_parse(input.parse::<i32>(), &input).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!("failed to parse \"{}...\"", "é".repeat(40))
+    );
+}
+
+#[test]
+fn from_parse_exact_length_not_truncated() {
+    let input = "y".repeat(40);
+    let err = from_parse(input.parse::<u8>(), &input).unwrap_err();
+
+    assert!(!err.to_string().contains("..."));
+}
@@ -0,0 +1,59 @@
+//! Tests for from_status() and http_status()
+
+#![cfg(feature = "http")]
+
+use http::StatusCode;
+use okerr::{Context, Result, anyerr, from_status, http_status};
+
+#[test]
+fn from_status_message() {
+    let err = from_status(StatusCode::NOT_FOUND, None);
+
+    assert_eq!(err.to_string(), "HTTP 404 Not Found");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn from_status_body_in_chain() {
+    let err = from_status(
+        StatusCode::BAD_REQUEST,
+        Some(r#"{"error":"invalid email"}"#.to_string()),
+    );
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(
+        chain,
+        ["HTTP 400 Bad Request", r#"{"error":"invalid email"}"#]
+    );
+}
+
+#[test]
+fn from_status_unknown_reason() {
+    let err = from_status(StatusCode::from_u16(599).unwrap(), None);
+    assert_eq!(err.to_string(), "HTTP 599");
+}
+
+#[test]
+fn http_status_returns_code() {
+    let err = from_status(StatusCode::TOO_MANY_REQUESTS, Some("slow down".into()));
+    assert_eq!(http_status(&err), Some(StatusCode::TOO_MANY_REQUESTS));
+
+    let err = from_status(StatusCode::BAD_GATEWAY, None);
+    assert_eq!(http_status(&err).map(|status| status.as_u16()), Some(502));
+}
+
+#[test]
+fn http_status_survives_context() {
+    let result: Result<()> =
+        Err(from_status(StatusCode::UNAUTHORIZED, None)).context("fetching profile");
+
+    assert_eq!(
+        http_status(&result.unwrap_err()),
+        Some(StatusCode::UNAUTHORIZED)
+    );
+}
+
+#[test]
+fn http_status_none_for_other_errors() {
+    assert_eq!(http_status(&anyerr!("not http")), None);
+}
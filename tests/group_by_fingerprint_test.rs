@@ -0,0 +1,44 @@
+//! Tests for group_by_fingerprint()
+
+use okerr::{anyerr, fingerprint, group_by_fingerprint};
+
+#[test]
+fn equal_chains_share_a_bucket() {
+    let errors = vec![
+        anyerr!("refused").context("connecting"),
+        anyerr!("disk full"),
+        anyerr!("refused").context("connecting"),
+        anyerr!("refused").context("reconnecting"),
+        anyerr!("disk full"),
+        anyerr!("refused").context("connecting"),
+    ];
+
+    let groups = group_by_fingerprint(errors);
+
+    let summary: Vec<(String, usize)> = groups
+        .iter()
+        .map(|(_, errors)| (format!("{:#}", errors[0]), errors.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("connecting: refused".to_string(), 3),
+            ("disk full".to_string(), 2),
+            ("reconnecting: refused".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn group_keys_are_fingerprints() {
+    let groups = group_by_fingerprint(vec![anyerr!("a"), anyerr!("b")]);
+
+    for (key, errors) in &groups {
+        assert!(errors.iter().all(|err| fingerprint(err) == *key));
+    }
+}
+
+#[test]
+fn empty_input() {
+    assert!(group_by_fingerprint(Vec::new()).is_empty());
+}
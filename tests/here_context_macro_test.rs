@@ -0,0 +1,38 @@
+//! Tests for the here_context! macro
+
+use okerr::{Result, anyerr, here_context};
+use std::io;
+
+#[test]
+fn here_context_attaches_file_and_line() {
+    let line = line!() + 1;
+    let result: Result<()> = here_context!(Err(anyerr!("disk full")));
+
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("tests/here_context_macro_test.rs:{line}")
+    );
+    assert_eq!(err.root_cause().to_string(), "disk full");
+}
+
+#[test]
+fn here_context_works_with_std_errors() {
+    fn read() -> Result<String> {
+        let content = here_context!(Err::<String, _>(io::Error::other("denied")))?;
+        Ok(content)
+    }
+
+    let err = read().unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("tests/here_context_macro_test.rs:")
+    );
+    assert!(err.downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn here_context_keeps_ok() {
+    let result: Result<u8> = here_context!(Ok::<u8, io::Error>(7));
+    assert_eq!(result.unwrap(), 7);
+}
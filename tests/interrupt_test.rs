@@ -0,0 +1,83 @@
+//! Tests for ResultExt::or_interrupt() and signal::was_interrupted()
+
+use okerr::signal::{InterruptError, was_interrupted};
+use okerr::{Context, Result, ResultExt, anyerr, derive::Error};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn copy_file() -> Result<()> {
+    Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe")).context("copying data")
+}
+
+#[test]
+fn or_interrupt_flag_set_replaces_error() {
+    let flag = AtomicBool::new(true);
+    let err = copy_file().or_interrupt(&flag).unwrap_err();
+
+    assert!(was_interrupted(&err));
+    assert!(err.is::<InterruptError>());
+    assert_eq!(err.to_string(), "interrupted");
+}
+
+#[test]
+fn or_interrupt_flag_unset_passes_through() {
+    let flag = AtomicBool::new(false);
+    let err = copy_file().or_interrupt(&flag).unwrap_err();
+
+    assert!(!was_interrupted(&err));
+    assert_eq!(format!("{err:#}"), "copying data: broken pipe");
+}
+
+#[test]
+fn or_interrupt_reads_flag_at_call_time() {
+    let flag = AtomicBool::new(false);
+    assert!(!was_interrupted(
+        &copy_file().or_interrupt(&flag).unwrap_err()
+    ));
+
+    // e.g. set by a Ctrl-C handler
+    flag.store(true, Ordering::SeqCst);
+    assert!(was_interrupted(
+        &copy_file().or_interrupt(&flag).unwrap_err()
+    ));
+}
+
+#[test]
+fn was_interrupted_through_context() {
+    let flag = AtomicBool::new(true);
+    let err = copy_file()
+        .or_interrupt(&flag)
+        .context("running backup")
+        .unwrap_err();
+
+    assert!(was_interrupted(&err));
+}
+
+#[test]
+fn or_interrupt_ok_passes_through() {
+    let flag = AtomicBool::new(true);
+    let result: Result<i32> = Ok(1);
+
+    assert_eq!(result.or_interrupt(&flag).unwrap(), 1);
+    assert!(!was_interrupted(&anyerr!("other")));
+}
+
+#[test]
+fn was_interrupted_deep_in_chain() {
+    #[derive(Error, Debug)]
+    #[error("job aborted")]
+    struct Aborted(#[source] InterruptError);
+
+    let err = okerr::Error::new(Aborted(InterruptError)).context("running backup");
+
+    assert!(was_interrupted(&err));
+}
+
+#[test]
+fn was_interrupted_after_marking() {
+    let flag = AtomicBool::new(true);
+    let result: Result<()> = Err(anyerr!("broken pipe"));
+    let err = result.or_interrupt(&flag).mark_system().unwrap_err();
+
+    assert!(was_interrupted(&err));
+}
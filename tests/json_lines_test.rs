@@ -0,0 +1,35 @@
+//! Tests for to_json_lines()
+
+#![cfg(feature = "serde_json")]
+
+use okerr::{anyerr, anyerr_chain, to_json_lines};
+use serde_json::Value;
+
+#[test]
+fn one_line_per_level() {
+    let err = anyerr_chain(["root", "middle", "outer"].map(String::from));
+    let jsonl = to_json_lines(&err);
+
+    let lines: Vec<Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), err.chain().count());
+
+    for (i, (line, expected)) in lines.iter().zip(["outer", "middle", "root"]).enumerate() {
+        assert_eq!(line["level"], i);
+        assert_eq!(line["message"], expected);
+    }
+}
+
+#[test]
+fn messages_are_escaped() {
+    let err = anyerr!("bad \"quote\"\nand newline");
+    let jsonl = to_json_lines(&err);
+
+    assert_eq!(jsonl.lines().count(), 1);
+
+    let line: Value = serde_json::from_str(&jsonl).unwrap();
+    assert_eq!(line["message"], "bad \"quote\"\nand newline");
+}
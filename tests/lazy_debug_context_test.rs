@@ -0,0 +1,76 @@
+//! Tests for ResultExt::lazy_debug_context()
+
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn context_in_debug_dump_only() {
+    let result: Result<()> = Err(anyerr!("query failed"));
+    let err = result
+        .lazy_debug_context(|| "plan: seq scan on orders".to_string())
+        .context("loading orders")
+        .unwrap_err();
+
+    assert_eq!(format!("{err}"), "loading orders");
+    assert_eq!(format!("{err:#}"), "loading orders: query failed");
+    assert!(!format!("{err:?}").contains("seq scan"));
+
+    let dump = format!("{err:#?}");
+    assert!(dump.contains("Debug context:"), "{dump}");
+    assert!(dump.contains("plan: seq scan on orders"), "{dump}");
+}
+
+#[test]
+fn closure_runs_only_in_debug_dump() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    let result: Result<()> = Err(anyerr!("query failed"));
+    let err = result
+        .lazy_debug_context(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            "heavy".to_string()
+        })
+        .unwrap_err();
+
+    let _ = format!("{err}");
+    let _ = format!("{err:#}");
+    let _ = format!("{err:?}");
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let _ = format!("{err:#?}");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn several_contexts_and_other_markers() {
+    let result: Result<()> = Err(anyerr!("bad input"));
+    let err = result
+        .lazy_debug_context(|| "first".to_string())
+        .mark_user()
+        .lazy_debug_context(|| "second".to_string())
+        .unwrap_err();
+
+    assert!(okerr::is_user_error(&err));
+    assert_eq!(err.chain().count(), 1);
+    assert!(format!("{err:#?}").contains("Debug context:\n    first\n    second"));
+}
+
+#[test]
+fn chain_is_unchanged() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::TimedOut, "db timeout").into());
+    let err = result
+        .context("fetching user")
+        .lazy_debug_context(|| "pool: 10/10 busy".to_string())
+        .unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["fetching user", "db timeout"]);
+    assert_eq!(err.root_cause().to_string(), "db timeout");
+    assert_eq!(
+        okerr::fingerprint(&err),
+        okerr::fingerprint(&anyerr!("db timeout").context("fetching user"))
+    );
+}
@@ -0,0 +1,63 @@
+//! Tests for Localizer and ResultExt::localize()
+
+use okerr::{Context, Localizer, Result, ResultExt, anyerr};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+struct Catalog(HashMap<&'static str, &'static str>);
+
+impl Catalog {
+    fn french() -> Self {
+        Self(HashMap::from([
+            ("file not found", "fichier introuvable"),
+            (
+                "saving profile",
+                "échec de l'enregistrement du profil ({0})",
+            ),
+        ]))
+    }
+}
+
+impl Localizer for Catalog {
+    fn translate(&self, key: &str, args: &[&dyn Display]) -> Option<String> {
+        let template = self.0.get(key)?;
+        let first = args.first().map(ToString::to_string).unwrap_or_default();
+        Some(template.replace("{0}", &first))
+    }
+}
+
+#[test]
+fn localize_translates_known_key() {
+    let result: Result<()> = Err(anyerr!("file not found"));
+    let err = result.localize(&Catalog::french()).unwrap_err();
+
+    assert_eq!(err.to_string(), "fichier introuvable");
+    assert_eq!(format!("{err:#}"), "fichier introuvable: file not found");
+}
+
+#[test]
+fn localize_passes_causes_as_args() {
+    let result: Result<()> = Err(anyerr!("disk full")).context("saving profile");
+    let err = result.localize(&Catalog::french()).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "échec de l'enregistrement du profil (disk full)"
+    );
+    assert_eq!(err.root_cause().to_string(), "disk full");
+}
+
+#[test]
+fn localize_leaves_unknown_key_unchanged() {
+    let result: Result<()> = Err(anyerr!("connection reset"));
+    let err = result.localize(&Catalog::french()).unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "connection reset");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn localize_keeps_ok() {
+    let result: Result<u8> = Ok(1);
+    assert_eq!(result.localize(&Catalog::french()).unwrap(), 1);
+}
@@ -0,0 +1,48 @@
+//! Tests for ResultExt::log_at()
+
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::Capture;
+use okerr::{Context, Result, ResultExt, anyerr};
+use tracing::Level;
+
+#[test]
+fn log_at_respects_level() {
+    for level in [
+        Level::ERROR,
+        Level::WARN,
+        Level::INFO,
+        Level::DEBUG,
+        Level::TRACE,
+    ] {
+        let capture = Capture::default();
+
+        let result: Result<()> = capture.run(|| {
+            Err(anyerr!("connection reset"))
+                .context("syncing")
+                .log_at(level)
+        });
+
+        assert_eq!(
+            format!("{:#}", result.unwrap_err()),
+            "syncing: connection reset"
+        );
+
+        let events = capture.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, level);
+        assert_eq!(events[0].message, "syncing: connection reset");
+    }
+}
+
+#[test]
+fn log_at_ok_is_silent() {
+    let capture = Capture::default();
+
+    let result: Result<i32> = capture.run(|| Ok(1).log_at(Level::ERROR));
+
+    assert_eq!(result.unwrap(), 1);
+    assert!(capture.events().is_empty());
+}
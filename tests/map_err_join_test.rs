@@ -0,0 +1,45 @@
+//! Tests for ResultExt::map_err_join()
+
+use okerr::{Context, Result, ResultExt, anyerr, derive::Error};
+use std::io;
+
+#[derive(Error, Debug)]
+#[error("rollback failed for transaction {0}")]
+struct RollbackError(u32);
+
+fn commit() -> Result<()> {
+    Err(io::Error::new(io::ErrorKind::TimedOut, "write timed out"))
+        .context("committing transaction")
+}
+
+#[test]
+fn map_err_join_keeps_both_messages() {
+    let err = commit().map_err_join(RollbackError(7)).unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(
+        chain,
+        [
+            "rollback failed for transaction 7",
+            "committing transaction",
+            "write timed out"
+        ]
+    );
+}
+
+#[test]
+fn map_err_join_secondary_is_downcastable() {
+    let err = commit().map_err_join(RollbackError(3)).unwrap_err();
+
+    assert_eq!(err.downcast_ref::<RollbackError>().unwrap().0, 3);
+    assert_eq!(err.root_cause().to_string(), "write timed out");
+}
+
+#[test]
+fn map_err_join_ok_passes_through() {
+    let result: Result<i32> = Ok(1);
+    assert_eq!(result.map_err_join(RollbackError(1)).unwrap(), 1);
+
+    let result: Result<i32> = Err(anyerr!("primary"));
+    assert!(result.map_err_join(io::Error::other("secondary")).is_err());
+}
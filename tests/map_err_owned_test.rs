@@ -0,0 +1,49 @@
+//! Tests for ResultExt::map_err_owned()
+
+use okerr::derive::Error;
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::io;
+
+#[derive(Error, Debug)]
+enum AppError {
+    #[error("not found")]
+    NotFound(#[source] io::Error),
+    #[error("internal: {0:#}")]
+    Internal(okerr::Error),
+}
+
+fn classify(err: okerr::Error) -> AppError {
+    match err.downcast::<io::Error>() {
+        Ok(io_err) if io_err.kind() == io::ErrorKind::NotFound => AppError::NotFound(io_err),
+        Ok(io_err) => AppError::Internal(io_err.into()),
+        Err(err) => AppError::Internal(err),
+    }
+}
+
+#[test]
+fn mapper_receives_owned_error_and_downcasts() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "no file").into());
+
+    match result.map_err_owned(classify) {
+        Err(AppError::NotFound(io_err)) => assert_eq!(io_err.to_string(), "no file"),
+        other => panic!("unexpected {other:?}"),
+    }
+}
+
+#[test]
+fn failed_downcast_keeps_error() {
+    let result: Result<()> = Err(anyerr!("boom")).context("loading");
+
+    match result.map_err_owned(classify) {
+        Err(AppError::Internal(err)) => assert_eq!(format!("{err:#}"), "loading: boom"),
+        other => panic!("unexpected {other:?}"),
+    }
+}
+
+#[test]
+fn ok_is_unchanged() {
+    let result: Result<u8> = Ok(1);
+    let mapped: std::result::Result<u8, String> =
+        result.map_err_owned(|_| panic!("must not be called"));
+    assert_eq!(mapped, Ok(1));
+}
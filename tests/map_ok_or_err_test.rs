@@ -0,0 +1,29 @@
+//! Tests for ResultExt::map_ok_or_err()
+
+use okerr::{Error, Result, ResultExt, anyerr};
+
+#[test]
+fn ok_arm() {
+    let result: Result<u32> = Ok(2);
+    let out = result.map_ok_or_err(|n| n * 10, |_| 0);
+
+    assert_eq!(out, 20);
+}
+
+#[test]
+fn err_arm() {
+    let result: Result<u32> = Err(anyerr!("boom"));
+    let out = result.map_ok_or_err(|n| n.to_string(), |err| format!("error: {err}"));
+
+    assert_eq!(out, "error: boom");
+}
+
+#[test]
+fn err_mapper_receives_owned_error() {
+    let result: Result<()> = Err(anyerr!("root").context("outer"));
+    let err: Option<Error> = result.map_ok_or_err(|_| None, Some);
+
+    let err = err.unwrap();
+    assert_eq!(err.to_string(), "outer");
+    assert_eq!(err.root_cause().to_string(), "root");
+}
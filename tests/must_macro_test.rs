@@ -0,0 +1,79 @@
+//! Tests for must! macro and OptionExt::ok_or_fail()
+
+use okerr::{OptionExt, Result, must};
+
+#[test]
+fn must_macro_binds_some() {
+    fn operation(value: Option<i32>) -> Result<i32> {
+        let value = must!(value, "value is missing");
+        Ok(value * 2)
+    }
+
+    assert_eq!(operation(Some(21)).unwrap(), 42);
+}
+
+#[test]
+fn must_macro_returns_early_on_none() {
+    fn operation(value: Option<i32>, reached: &mut bool) -> Result<i32> {
+        let value = must!(value, "value is missing");
+        *reached = true;
+        Ok(value)
+    }
+
+    let mut reached = false;
+    let result = operation(None, &mut reached);
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().to_string(), "value is missing");
+    assert!(!reached);
+}
+
+#[test]
+fn must_macro_with_format() {
+    fn lookup(key: &str) -> Result<&'static str> {
+        let value = must!(None::<&'static str>, "key '{}' not found", key);
+        Ok(value)
+    }
+
+    assert_eq!(
+        lookup("name").unwrap_err().to_string(),
+        "key 'name' not found"
+    );
+}
+
+#[test]
+fn must_macro_value_usable_afterward() {
+    fn operation() -> Result<String> {
+        let mut name = must!(Some("okerr".to_string()), "no name");
+        name.push_str(" rocks");
+        Ok(name)
+    }
+
+    assert_eq!(operation().unwrap(), "okerr rocks");
+}
+
+#[test]
+fn ok_or_fail_some() {
+    let result = Some(42).ok_or_fail("missing");
+    assert_eq!(result.unwrap(), 42);
+}
+
+#[test]
+fn ok_or_fail_none() {
+    let result = None::<i32>.ok_or_fail("missing value");
+    assert_eq!(result.unwrap_err().to_string(), "missing value");
+}
+
+#[test]
+fn ok_or_fail_with_question_mark() {
+    fn operation(value: Option<&str>) -> Result<usize> {
+        let value = value.ok_or_fail(format!("no value for {}", "input"))?;
+        Ok(value.len())
+    }
+
+    assert_eq!(operation(Some("abc")).unwrap(), 3);
+    assert_eq!(
+        operation(None).unwrap_err().to_string(),
+        "no value for input"
+    );
+}
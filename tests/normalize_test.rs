@@ -0,0 +1,38 @@
+//! Tests for normalize()
+
+use okerr::{anyerr, normalize};
+use std::io;
+
+#[test]
+fn normalize_flattens_multiline_messages() {
+    let err = anyerr!("unexpected token\n  --> line 3, column 7\n   |\n 3 | foo = ")
+        .context("parsing config");
+    let err = normalize(err);
+
+    assert_eq!(
+        format!("{err:#}"),
+        "parsing config: unexpected token --> line 3, column 7 | 3 | foo ="
+    );
+    assert!(!format!("{err:#}").contains('\n'));
+}
+
+#[test]
+fn normalize_collapses_spaces_and_tabs() {
+    let err = normalize(anyerr!("  too   many\tspaces  "));
+    assert_eq!(err.to_string(), "too many spaces");
+}
+
+#[test]
+fn normalize_preserves_chain_depth() {
+    let err: okerr::Error = io::Error::other("disk\nfull").into();
+    let err = normalize(err.context("writing\r\nfile").context("saving"));
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["saving", "writing file", "disk full"]);
+}
+
+#[test]
+fn normalize_clean_messages_unchanged() {
+    let err = normalize(anyerr!("already clean").context("outer"));
+    assert_eq!(format!("{err:#}"), "outer: already clean");
+}
@@ -0,0 +1,52 @@
+//! Tests for OptionExt::ok_or_context()
+
+use okerr::{OptionExt, Result};
+use std::collections::HashMap;
+
+#[test]
+fn some_becomes_ok() {
+    assert_eq!(Some(3).ok_or_context("missing").unwrap(), 3);
+}
+
+#[test]
+fn none_becomes_error_with_context() {
+    let err = None::<u8>.ok_or_context("missing port").unwrap_err();
+
+    assert_eq!(err.to_string(), "missing port");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn chains_with_question_mark() {
+    fn port(config: &HashMap<&str, &str>) -> Result<u16> {
+        let raw = config.get("port").ok_or_context("port is not configured")?;
+        Ok(raw.parse()?)
+    }
+
+    let config = HashMap::from([("port", "8080")]);
+    assert_eq!(port(&config).unwrap(), 8080);
+
+    let err = port(&HashMap::new()).unwrap_err();
+    assert_eq!(err.to_string(), "port is not configured");
+}
+
+#[test]
+fn usable_in_higher_order_code() {
+    let names = ["alice", "", "bob"];
+
+    let initials: Vec<Result<char>> = names
+        .iter()
+        .map(|name| {
+            name.chars()
+                .next()
+                .ok_or_context(format!("empty name {name:?}"))
+        })
+        .collect();
+
+    assert_eq!(initials[0].as_ref().unwrap(), &'a');
+    assert_eq!(
+        initials[1].as_ref().unwrap_err().to_string(),
+        "empty name \"\""
+    );
+    assert_eq!(initials[2].as_ref().unwrap(), &'b');
+}
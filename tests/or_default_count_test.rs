@@ -0,0 +1,53 @@
+//! Tests for ResultExt::or_default_count()
+
+use okerr::{Result, ResultExt, anyerr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn lookup(key: &str) -> Result<u32> {
+    match key {
+        "a" => Ok(1),
+        "b" => Ok(2),
+        _ => Err(anyerr!("unknown key {key}")),
+    }
+}
+
+#[test]
+fn counter_matches_defaults_returned() {
+    let fallbacks = AtomicUsize::new(0);
+
+    let values: Vec<u32> = ["a", "x", "b", "y", "z"]
+        .iter()
+        .map(|key| lookup(key).or_default_count(&fallbacks))
+        .collect();
+
+    assert_eq!(values, [1, 0, 2, 0, 0]);
+    assert_eq!(fallbacks.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn ok_does_not_count() {
+    let fallbacks = AtomicUsize::new(0);
+    let result: Result<String> = Ok("value".to_string());
+
+    assert_eq!(result.or_default_count(&fallbacks), "value");
+    assert_eq!(fallbacks.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn shared_counter_across_threads() {
+    static FALLBACKS: AtomicUsize = AtomicUsize::new(0);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let result: Result<Vec<u8>> = Err(anyerr!("unavailable"));
+                result.or_default_count(&FALLBACKS)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert!(handle.join().unwrap().is_empty());
+    }
+    assert_eq!(FALLBACKS.load(Ordering::Relaxed), 4);
+}
@@ -0,0 +1,50 @@
+//! Tests for ResultExt::or_try()
+
+use okerr::{Context, MultiError, Result, ResultExt, anyerr, err};
+use std::cell::Cell;
+
+#[test]
+fn or_try_primary_success_short_circuits() {
+    let called = Cell::new(false);
+    let primary: Result<i32> = Ok(1);
+
+    let value = primary
+        .or_try(|| {
+            called.set(true);
+            Ok(2)
+        })
+        .unwrap();
+
+    assert_eq!(value, 1);
+    assert!(!called.get());
+}
+
+#[test]
+fn or_try_fallback_recovers() {
+    let primary: Result<i32> = err!("primary unavailable");
+    assert_eq!(primary.or_try(|| Ok(2)).unwrap(), 2);
+}
+
+#[test]
+fn or_try_double_failure_surfaces_both() {
+    let primary: Result<i32> = Err(anyerr!("connection refused")).context("reading replica");
+    let err = primary.or_try(|| err!("disk full")).unwrap_err();
+
+    let multi = err.downcast_ref::<MultiError>().unwrap();
+    assert_eq!(multi.len(), 2);
+    assert_eq!(
+        err.to_string(),
+        "2 errors occurred:\n  - reading replica: connection refused\n  - disk full"
+    );
+}
+
+#[test]
+fn or_try_chained() {
+    let primary: Result<&str> = err!("env");
+    let value = primary
+        .or_try(|| err!("file"))
+        .or_try(|| Ok("defaults"))
+        .unwrap();
+
+    assert_eq!(value, "defaults");
+}
@@ -0,0 +1,43 @@
+//! Tests for panic_hook::install_panic_hook() and panic_hook::uninstall_panic_hook()
+
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::Capture;
+use okerr::panic_hook::{install_panic_hook, uninstall_panic_hook};
+use std::panic;
+use tracing::Level;
+
+// The panic hook is global, so everything is checked in one test
+// to not interfere with other tests.
+#[test]
+fn panic_hook_logs_panics_until_uninstalled() {
+    let capture = Capture::default();
+
+    install_panic_hook();
+    // Installing twice keeps the original hook
+    install_panic_hook();
+
+    let result = capture.run(|| panic::catch_unwind(|| panic!("boom {}", 42)));
+    assert!(result.is_err());
+
+    let events = capture.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].level, Level::ERROR);
+    assert_eq!(events[0].message, "panicked: boom 42");
+
+    let (name, location) = &events[0].fields[0];
+    assert_eq!(name, "location");
+    assert!(location.starts_with("tests/panic_hook_test.rs:"));
+
+    let result = capture.run(|| panic::catch_unwind(|| panic!("static message")));
+    assert!(result.is_err());
+    assert_eq!(capture.events()[1].message, "panicked: static message");
+
+    uninstall_panic_hook();
+
+    let result = capture.run(|| panic::catch_unwind(|| panic!("not logged")));
+    assert!(result.is_err());
+    assert_eq!(capture.events().len(), 2);
+}
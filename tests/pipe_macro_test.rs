@@ -0,0 +1,60 @@
+//! Tests for the pipe! macro
+
+use okerr::{Result, ensure, pipe};
+
+fn parse(s: &str) -> Result<i64> {
+    Ok(s.trim().parse()?)
+}
+
+fn positive(n: i64) -> Result<i64> {
+    ensure!(n > 0, "{n} is not positive");
+    Ok(n)
+}
+
+fn double(n: i64) -> Result<i64> {
+    n.checked_mul(2).ok_or_else(|| okerr::anyerr!("overflow"))
+}
+
+#[test]
+fn pipe_threads_value_through_steps() {
+    assert_eq!(pipe!("21" => parse => positive => double).unwrap(), 42);
+}
+
+#[test]
+fn pipe_single_step() {
+    assert_eq!(pipe!("7" => parse).unwrap(), 7);
+}
+
+#[test]
+fn pipe_failing_middle_step_is_named() {
+    let err = pipe!("-3" => parse => positive => double).unwrap_err();
+
+    assert_eq!(err.to_string(), "step `positive` failed");
+    assert_eq!(err.root_cause().to_string(), "-3 is not positive");
+}
+
+#[test]
+fn pipe_failing_first_step_is_named() {
+    let err = pipe!("abc" => parse => positive).unwrap_err();
+    assert_eq!(err.to_string(), "step `parse` failed");
+}
+
+#[test]
+fn pipe_with_std_errors_and_closures() {
+    let result = pipe!("0x1f" => |s: &str| u8::from_str_radix(s.trim_start_matches("0x"), 16));
+    assert_eq!(result.unwrap(), 31);
+
+    let err = pipe!("zz" => |s: &str| s.parse::<u8>()).unwrap_err();
+    assert_eq!(err.to_string(), "step `|s: &str| s.parse::<u8>()` failed");
+}
+
+#[test]
+fn pipe_propagates_with_question_mark() {
+    fn run(input: &str) -> Result<i64> {
+        let n = pipe!(input => parse => double)?;
+        Ok(n + 1)
+    }
+
+    assert_eq!(run("4").unwrap(), 9);
+    assert!(run("x").is_err());
+}
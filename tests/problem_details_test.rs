@@ -0,0 +1,57 @@
+//! Tests for to_problem_details()
+
+#![cfg(all(feature = "http", feature = "serde"))]
+
+use okerr::{ProblemDetails, anyerr, to_problem_details};
+use serde_json::json;
+
+#[test]
+fn problem_details_fields() {
+    let err = anyerr!("email already taken").context("creating account");
+    let problem = to_problem_details(&err, 409);
+
+    assert_eq!(
+        problem,
+        ProblemDetails {
+            type_: "about:blank".to_string(),
+            title: "Conflict".to_string(),
+            status: 409,
+            detail: "creating account: email already taken".to_string(),
+        }
+    );
+}
+
+#[test]
+fn problem_details_serializes_per_rfc() {
+    let err = anyerr!("database unreachable");
+    let value = serde_json::to_value(to_problem_details(&err, 503)).unwrap();
+
+    assert_eq!(
+        value,
+        json!({
+            "type": "about:blank",
+            "title": "Service Unavailable",
+            "status": 503,
+            "detail": "database unreachable",
+        })
+    );
+}
+
+#[test]
+fn problem_details_unknown_status() {
+    let problem = to_problem_details(&anyerr!("teapot overflow"), 599);
+
+    assert_eq!(problem.title, "Unknown Status");
+    assert_eq!(problem.status, 599);
+}
+
+#[test]
+fn problem_details_round_trip() {
+    let problem = to_problem_details(&anyerr!("bad input"), 400);
+    let json = serde_json::to_string(&problem).unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<ProblemDetails>(&json).unwrap(),
+        problem
+    );
+}
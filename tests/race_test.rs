@@ -0,0 +1,93 @@
+//! Tests for race()
+
+#![cfg(feature = "future")]
+
+use futures::executor::block_on;
+use futures::future::{self, BoxFuture, FutureExt};
+use okerr::{MultiError, Result, anyerr, race};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Resolve to the result after being polled `polls` times.
+struct Delayed<T> {
+    polls: usize,
+    result: Option<Result<T>>,
+}
+
+impl<T: Unpin> Future for Delayed<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.polls == 0 {
+            return Poll::Ready(self.result.take().expect("polled after completion"));
+        }
+
+        self.polls -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn delayed<T: Unpin + Send + 'static>(
+    polls: usize,
+    result: Result<T>,
+) -> BoxFuture<'static, Result<T>> {
+    Delayed {
+        polls,
+        result: Some(result),
+    }
+    .boxed()
+}
+
+#[test]
+fn race_first_success_wins() {
+    let winner = race(vec![
+        delayed(5, Ok("slow")),
+        delayed(1, Ok("fast")),
+        delayed(0, Err(anyerr!("broken"))),
+    ]);
+
+    assert_eq!(block_on(winner).unwrap(), "fast");
+}
+
+#[test]
+fn race_success_after_failures() {
+    let winner = race(vec![
+        delayed(0, Err(anyerr!("a down"))),
+        delayed(3, Ok(42)),
+        delayed(1, Err(anyerr!("b down"))),
+    ]);
+
+    assert_eq!(block_on(winner).unwrap(), 42);
+}
+
+#[test]
+fn race_all_fail_aggregates_in_order() {
+    let all_failed = race::<()>(vec![
+        delayed(2, Err(anyerr!("first down"))),
+        delayed(0, Err(anyerr!("second down"))),
+        delayed(1, Err(anyerr!("third down"))),
+    ]);
+
+    let err = block_on(all_failed).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "3 errors occurred:\n  - first down\n  - second down\n  - third down"
+    );
+    assert_eq!(err.downcast_ref::<MultiError>().unwrap().len(), 3);
+}
+
+#[test]
+fn race_empty_fails() {
+    let err = block_on(race::<()>(Vec::new())).unwrap_err();
+    assert!(err.downcast_ref::<MultiError>().unwrap().is_empty());
+}
+
+#[test]
+fn race_borrows_non_static_data() {
+    let name = String::from("local");
+    let winner = race(vec![future::ready(Ok(name.as_str())).boxed()]);
+
+    assert_eq!(block_on(winner).unwrap(), "local");
+}
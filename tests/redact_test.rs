@@ -0,0 +1,50 @@
+//! Tests for redact::anonymize_paths()
+
+use okerr::{anyerr, redact::anonymize_paths};
+
+#[test]
+fn unix_path_replaced() {
+    let err = anonymize_paths(anyerr!("cannot open /home/alice/file.txt for reading"));
+    assert_eq!(err.to_string(), "cannot open <path> for reading");
+}
+
+#[test]
+fn windows_path_replaced() {
+    let err = anonymize_paths(anyerr!(r"cannot open C:\Users\alice\file.txt"));
+    assert_eq!(err.to_string(), "cannot open <path>");
+
+    let err = anonymize_paths(anyerr!("cannot open D:/data/file.txt"));
+    assert_eq!(err.to_string(), "cannot open <path>");
+}
+
+#[test]
+fn quoted_and_punctuated_paths() {
+    let err = anonymize_paths(anyerr!(
+        "file \"/home/alice/a.txt\" (see /tmp/b.log): denied, /etc/c.conf; done"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "file \"<path>\" (see <path>): denied, <path>; done"
+    );
+}
+
+#[test]
+fn non_paths_preserved() {
+    let msg = "read/write failed and / or https://example.com/x is down";
+    let err = anonymize_paths(anyerr!("{msg}"));
+    assert_eq!(err.to_string(), msg);
+}
+
+#[test]
+fn whole_chain_rewritten() {
+    let err = anyerr!("/home/alice/app.toml: permission denied")
+        .context("loading /home/alice/.config")
+        .context("starting");
+    let err = anonymize_paths(err);
+
+    let messages: Vec<String> = err.chain().map(|c| c.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["starting", "loading <path>", "<path>: permission denied"]
+    );
+}
@@ -0,0 +1,49 @@
+//! Tests for RequestContext and ResultExt::request_context()
+
+#![cfg(feature = "http")]
+
+use okerr::{Context, RequestContext, Result, ResultExt, anyerr, request_context_of};
+
+fn request() -> RequestContext {
+    RequestContext {
+        method: "POST".to_string(),
+        path: "/orders".to_string(),
+        headers: vec![
+            ("authorization".to_string(), "Bearer secret".to_string()),
+            ("x-request-id".to_string(), "r-123".to_string()),
+        ],
+    }
+}
+
+#[test]
+fn request_context_display_summary() {
+    let result: Result<()> = Err(anyerr!("out of stock")).context("creating order");
+    let err = result.request_context(&request()).unwrap_err();
+
+    assert_eq!(
+        format!("{err:#}"),
+        "request POST /orders: creating order: out of stock"
+    );
+    assert!(!format!("{err:?}").contains("secret"));
+}
+
+#[test]
+fn request_context_recovered_after_more_context() {
+    let result: Result<()> = Err(anyerr!("out of stock"));
+    let err = result
+        .request_context(&request())
+        .context("handler failed")
+        .unwrap_err();
+
+    let ctx = request_context_of(&err).unwrap();
+    assert_eq!(ctx, &request());
+    assert_eq!(
+        ctx.headers[1],
+        ("x-request-id".to_string(), "r-123".to_string())
+    );
+}
+
+#[test]
+fn request_context_absent() {
+    assert_eq!(request_context_of(&anyerr!("boom")), None);
+}
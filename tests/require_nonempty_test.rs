@@ -0,0 +1,37 @@
+//! Tests for VecResultExt::require_nonempty()
+
+use okerr::{Context, Result, VecResultExt, anyerr};
+
+fn search(query: &str) -> Result<Vec<&'static str>> {
+    match query {
+        "" => Err(anyerr!("empty query")),
+        "a" => Ok(vec!["alice", "adam"]),
+        _ => Ok(Vec::new()),
+    }
+}
+
+#[test]
+fn empty_becomes_error() {
+    let err = search("zed")
+        .require_nonempty("no user matches \"zed\"")
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "no user matches \"zed\"");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn non_empty_unchanged() {
+    let users = search("a").require_nonempty("no user").unwrap();
+    assert_eq!(users, ["alice", "adam"]);
+}
+
+#[test]
+fn existing_error_unchanged() {
+    let err = search("")
+        .context("searching users")
+        .require_nonempty("no user")
+        .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "searching users: empty query");
+}
@@ -0,0 +1,61 @@
+//! Tests for ResultExt::retry_after() and retry_after()
+
+use okerr::{Context, Result, ResultExt, anyerr, retry_after};
+use std::time::Duration;
+
+fn rate_limited() -> Result<()> {
+    Err(anyerr!("HTTP 429 Too Many Requests"))
+}
+
+#[test]
+fn delay_survives_context_layers() {
+    let err = rate_limited()
+        .retry_after(Duration::from_secs(12))
+        .context("fetching rates")
+        .context("refreshing dashboard")
+        .unwrap_err();
+
+    assert_eq!(retry_after(&err), Some(Duration::from_secs(12)));
+    assert_eq!(
+        format!("{err:#}"),
+        "refreshing dashboard: fetching rates: HTTP 429 Too Many Requests"
+    );
+}
+
+#[test]
+fn last_delay_wins() {
+    let err = rate_limited()
+        .retry_after(Duration::from_secs(1))
+        .retry_after(Duration::from_millis(500))
+        .unwrap_err();
+
+    assert_eq!(retry_after(&err), Some(Duration::from_millis(500)));
+}
+
+#[test]
+fn no_delay() {
+    assert_eq!(retry_after(&anyerr!("boom")), None);
+}
+
+#[test]
+fn retry_loop_honors_delay() {
+    let mut waited = Duration::ZERO;
+    let mut attempts = 0;
+
+    let result = loop {
+        attempts += 1;
+        let result = if attempts < 3 {
+            rate_limited().retry_after(Duration::from_millis(100))
+        } else {
+            Ok(())
+        };
+
+        match result {
+            Ok(()) => break Ok::<_, okerr::Error>(()),
+            Err(err) => waited += retry_after(&err).unwrap_or(Duration::from_secs(1)),
+        }
+    };
+
+    assert!(result.is_ok());
+    assert_eq!(waited, Duration::from_millis(200));
+}
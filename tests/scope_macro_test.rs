@@ -0,0 +1,69 @@
+//! Tests for scope! macro
+
+use okerr::{Result, anyerr, ensure, scope};
+use std::cell::Cell;
+
+fn parse_pair(input: &str) -> Result<(i32, i32)> {
+    scope!(format!("parsing pair '{input}'"), {
+        let (a, b) = input
+            .split_once(',')
+            .ok_or_else(|| anyerr!("missing comma"))?;
+        let a: i32 = a.parse()?;
+        let b: i32 = b.parse()?;
+        ensure!(a <= b, "{a} is greater than {b}");
+        Ok((a, b))
+    })
+}
+
+#[test]
+fn scope_ok_passes_through() {
+    assert_eq!(parse_pair("1,2").unwrap(), (1, 2));
+}
+
+#[test]
+fn scope_adds_context_to_any_error() {
+    for (input, cause) in [
+        ("12", "missing comma"),
+        ("x,2", "invalid digit found in string"),
+        ("3,y", "invalid digit found in string"),
+        ("3,1", "3 is greater than 1"),
+    ] {
+        let err = parse_pair(input).unwrap_err();
+
+        assert_eq!(err.to_string(), format!("parsing pair '{input}'"));
+        assert_eq!(err.root_cause().to_string(), cause);
+    }
+}
+
+#[test]
+fn scope_context_is_lazy() {
+    let evaluated = Cell::new(false);
+
+    let result = scope!(
+        {
+            evaluated.set(true);
+            "context"
+        },
+        { Ok(1) }
+    );
+
+    assert_eq!(result.unwrap(), 1);
+    assert!(!evaluated.get());
+}
+
+#[test]
+fn scope_nested() {
+    let result: Result<()> = scope!("outer", {
+        scope!("inner", {
+            let value: i32 = "nope".parse()?;
+            let _ = value;
+            Ok(())
+        })?;
+        Ok(())
+    });
+
+    assert_eq!(
+        format!("{:#}", result.unwrap_err()),
+        "outer: inner: invalid digit found in string"
+    );
+}
@@ -0,0 +1,29 @@
+//! Tests for sequence()
+
+use okerr::{Result, anyerr, sequence};
+
+#[test]
+fn sequence_all_ok_in_order() {
+    let results: Vec<Result<&str>> = vec![Ok("a"), Ok("b"), Ok("c")];
+    assert_eq!(sequence(results).unwrap(), ["a", "b", "c"]);
+}
+
+#[test]
+fn sequence_empty() {
+    assert!(sequence::<u8>(Vec::new()).unwrap().is_empty());
+}
+
+#[test]
+fn sequence_failure_names_position_and_total() {
+    let results: Vec<Result<u8>> = vec![
+        Ok(1),
+        Ok(2),
+        Err(anyerr!("checksum mismatch")),
+        Ok(4),
+        Err(anyerr!("ignored")),
+    ];
+
+    let err = sequence(results).unwrap_err();
+    assert_eq!(err.to_string(), "element 2 of 5 failed");
+    assert_eq!(err.root_cause().to_string(), "checksum mismatch");
+}
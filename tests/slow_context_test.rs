@@ -0,0 +1,45 @@
+//! Tests for timing::slow_context()
+
+use okerr::{Result, anyerr, timing::slow_context};
+use std::thread;
+use std::time::Duration;
+
+fn slow<T>(result: Result<T>) -> Result<T> {
+    thread::sleep(Duration::from_millis(30));
+    result
+}
+
+#[test]
+fn slow_error_gets_context() {
+    let result: Result<()> = slow_context(Duration::from_millis(5), "slow upstream", || {
+        slow(Err(anyerr!("connection reset")))
+    });
+
+    let err = result.unwrap_err();
+    let top = err.to_string();
+    assert!(top.starts_with("slow upstream (took "), "{top}");
+    assert!(top.ends_with("ms)"), "{top}");
+    assert_eq!(err.root_cause().to_string(), "connection reset");
+}
+
+#[test]
+fn fast_error_unchanged() {
+    let result: Result<()> = slow_context(Duration::from_secs(10), "slow upstream", || {
+        Err(anyerr!("connection reset"))
+    });
+
+    let err = result.unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn slow_success_unchanged() {
+    let result = slow_context(Duration::from_millis(5), "slow upstream", || slow(Ok(7)));
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[test]
+fn fast_success_unchanged() {
+    let result = slow_context(Duration::from_secs(10), "slow upstream", || Ok("fast"));
+    assert_eq!(result.unwrap(), "fast");
+}
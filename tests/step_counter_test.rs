@@ -0,0 +1,53 @@
+//! Tests for StepCounter and ResultExt::step()
+
+use okerr::{Result, ResultExt, StepCounter, anyerr, ensure};
+
+fn parse(input: &str) -> Result<i64> {
+    Ok(input.trim().parse()?)
+}
+
+fn check(n: i64) -> Result<i64> {
+    ensure!(n > 0, "{n} is not positive");
+    Ok(n)
+}
+
+fn pipeline(input: &str) -> (Result<i64>, usize) {
+    let mut steps = StepCounter::new();
+
+    let result = (|| {
+        let n = parse(input).step(&mut steps, "parse")?;
+        let n = check(n).step(&mut steps, "check")?;
+        let n = n.checked_mul(2).ok_or_else(|| anyerr!("overflow"));
+        n.step(&mut steps, "double")
+    })();
+
+    (result, steps.steps())
+}
+
+#[test]
+fn all_steps_succeed() {
+    let (result, steps) = pipeline("21");
+
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(steps, 3);
+}
+
+#[test]
+fn failing_step_number_and_name() {
+    let (result, steps) = pipeline("-4");
+
+    assert_eq!(
+        format!("{:#}", result.unwrap_err()),
+        "step 2: check: -4 is not positive"
+    );
+    assert_eq!(steps, 2);
+}
+
+#[test]
+fn numbers_increment_across_steps() {
+    let (result, _) = pipeline("x");
+    assert_eq!(result.unwrap_err().to_string(), "step 1: parse");
+
+    let (result, _) = pipeline(&i64::MAX.to_string());
+    assert_eq!(result.unwrap_err().to_string(), "step 3: double");
+}
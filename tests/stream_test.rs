@@ -0,0 +1,59 @@
+//! Tests for stream::log_errors()
+
+#![cfg(feature = "future")]
+
+#[cfg(feature = "tracing")]
+mod common;
+
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use okerr::{Result, anyerr, stream::log_errors};
+
+fn items() -> Vec<Result<i32>> {
+    vec![
+        Ok(1),
+        Err(anyerr!("first failure")),
+        Ok(2),
+        Err(anyerr!("second failure")),
+    ]
+}
+
+#[test]
+fn log_errors_forwards_all_items() {
+    let forwarded: Vec<Result<i32>> = block_on(log_errors(stream::iter(items())).collect());
+
+    assert_eq!(forwarded.len(), 4);
+    assert_eq!(*forwarded[0].as_ref().unwrap(), 1);
+    assert_eq!(
+        forwarded[1].as_ref().unwrap_err().to_string(),
+        "first failure"
+    );
+    assert_eq!(*forwarded[2].as_ref().unwrap(), 2);
+    assert_eq!(
+        forwarded[3].as_ref().unwrap_err().to_string(),
+        "second failure"
+    );
+}
+
+#[test]
+fn log_errors_empty_stream() {
+    let forwarded: Vec<Result<i32>> = block_on(log_errors(stream::iter(Vec::new())).collect());
+    assert!(forwarded.is_empty());
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn log_errors_logs_each_error() {
+    let capture = common::Capture::default();
+
+    let forwarded: Vec<Result<i32>> =
+        capture.run(|| block_on(log_errors(stream::iter(items())).collect()));
+
+    assert_eq!(forwarded.len(), 4);
+
+    let events = capture.events();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.level == tracing::Level::ERROR));
+    assert_eq!(events[0].message, "first failure");
+    assert_eq!(events[1].message, "second failure");
+}
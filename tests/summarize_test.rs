@@ -0,0 +1,36 @@
+//! Tests for summarize()
+
+use okerr::{anyerr, summarize};
+
+#[test]
+fn truncates_at_word_boundary() {
+    let err = anyerr!("the quick brown fox jumps over the lazy dog");
+
+    assert_eq!(summarize(&err, 4), "the quick brown fox...");
+    assert_eq!(summarize(&err, 1), "the...");
+}
+
+#[test]
+fn short_message_intact() {
+    let err = anyerr!("disk  full");
+
+    assert_eq!(summarize(&err, 2), "disk  full");
+    assert_eq!(summarize(&err, 5), "disk  full");
+}
+
+#[test]
+fn only_top_message() {
+    let err = anyerr!("connection refused by remote host").context("syncing orders");
+    assert_eq!(summarize(&err, 5), "syncing orders");
+}
+
+#[test]
+fn multiline_message_collapsed_when_truncated() {
+    let err = anyerr!("parse error\nat line 3\ncolumn 7");
+    assert_eq!(summarize(&err, 3), "parse error at...");
+}
+
+#[test]
+fn zero_words() {
+    assert_eq!(summarize(&anyerr!("boom"), 0), "...");
+}
@@ -0,0 +1,53 @@
+//! Tests for ResultExt::tag(), ResultExt::tags() and tags_of()
+
+use okerr::{Context, Result, ResultExt, anyerr, tags_of};
+
+#[test]
+fn tags_accumulate_across_context() {
+    let result: Result<()> = Err(anyerr!("deadlock detected"));
+    let err = result
+        .tag("db")
+        .context("saving order")
+        .tags(&["retryable", "orders"])
+        .context("checkout")
+        .tag("critical")
+        .unwrap_err();
+
+    assert_eq!(tags_of(&err), ["db", "retryable", "orders", "critical"]);
+    assert_eq!(
+        format!("{err:#}"),
+        "checkout: saving order: deadlock detected"
+    );
+}
+
+#[test]
+fn tags_are_deduplicated() {
+    let result: Result<()> = Err(anyerr!("timeout"));
+    let err = result
+        .tag("net")
+        .tags(&["net", "slow"])
+        .tag("slow")
+        .unwrap_err();
+
+    assert_eq!(tags_of(&err), ["net", "slow"]);
+}
+
+#[test]
+fn untagged_error_has_no_tags() {
+    assert!(tags_of(&anyerr!("plain")).is_empty());
+}
+
+#[test]
+fn tags_keep_other_markers() {
+    let result: Result<()> = Err(anyerr!("bad input"));
+    let err = result.mark_user().tag("validation").unwrap_err();
+
+    assert!(okerr::is_user_error(&err));
+    assert_eq!(tags_of(&err), ["validation"]);
+}
+
+#[test]
+fn tag_on_ok_is_noop() {
+    let result: Result<u8> = Ok(1);
+    assert_eq!(result.tag("db").unwrap(), 1);
+}
@@ -0,0 +1,32 @@
+//! Tests for timing::timed_context()
+
+use okerr::{Result, anyerr, timing::timed_context};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn timed_context_ok_passes_through() {
+    let result = timed_context("computing", || Ok(42));
+    assert_eq!(result.unwrap(), 42);
+}
+
+#[test]
+fn timed_context_attaches_label_and_duration() {
+    let result: Result<()> = timed_context("loading index", || {
+        thread::sleep(Duration::from_millis(20));
+        Err(anyerr!("corrupted file"))
+    });
+
+    let err = result.unwrap_err();
+    let msg = err.to_string();
+
+    let ms = msg
+        .strip_prefix("loading index failed after ")
+        .and_then(|rest| rest.strip_suffix("ms"))
+        .unwrap()
+        .parse::<u128>()
+        .unwrap();
+
+    assert!(ms >= 20);
+    assert_eq!(err.root_cause().to_string(), "corrupted file");
+}
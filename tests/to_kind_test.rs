@@ -0,0 +1,70 @@
+//! Tests for ResultExt::to_kind()
+
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::cell::Cell;
+use std::io;
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    NotFound,
+    PermissionDenied,
+    Other,
+}
+
+impl Kind {
+    fn classify(err: &okerr::Error) -> Kind {
+        let io_kind = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<io::Error>())
+            .map(|io_err| io_err.kind());
+
+        match io_kind {
+            Some(io::ErrorKind::NotFound) => Kind::NotFound,
+            Some(io::ErrorKind::PermissionDenied) => Kind::PermissionDenied,
+            _ => Kind::Other,
+        }
+    }
+}
+
+#[test]
+fn to_kind_maps_error() {
+    let result: Result<()> = Err(anyerr!("boom"));
+    assert_eq!(result.to_kind(Kind::classify), Err(Kind::Other));
+}
+
+#[test]
+fn to_kind_inspects_chain() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "config.toml"))
+        .context("loading config")
+        .context("starting app");
+
+    assert_eq!(result.to_kind(Kind::classify), Err(Kind::NotFound));
+
+    let result: Result<()> =
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied")).context("writing file");
+
+    assert_eq!(result.to_kind(Kind::classify), Err(Kind::PermissionDenied));
+}
+
+#[test]
+fn to_kind_keeps_ok() {
+    let result: Result<i32> = Ok(42);
+    assert_eq!(result.to_kind(Kind::classify), Ok(42));
+}
+
+#[test]
+fn to_kind_classifier_only_runs_on_err() {
+    let calls = Cell::new(0);
+    let classify = |_: &okerr::Error| {
+        calls.set(calls.get() + 1);
+        Kind::Other
+    };
+
+    let ok: Result<i32> = Ok(1);
+    let _ = ok.to_kind(classify);
+    assert_eq!(calls.get(), 0);
+
+    let err: Result<i32> = Err(anyerr!("fail"));
+    let _ = err.to_kind(classify);
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,39 @@
+//! Tests for to_miette()
+
+#![cfg(feature = "miette")]
+
+use okerr::{Context, Result, anyerr, to_miette};
+use std::io;
+
+#[test]
+fn to_miette_keeps_message() {
+    let err = anyerr!("something failed");
+    let report = to_miette(&err);
+
+    assert_eq!(report.to_string(), "something failed");
+    assert_eq!(report.chain().count(), 1);
+}
+
+#[test]
+fn to_miette_keeps_causes() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "config.toml"))
+        .context("loading config")
+        .context("starting app");
+
+    let err = result.unwrap_err();
+    let report = to_miette(&err);
+
+    assert_eq!(report.to_string(), "starting app");
+
+    let chain: Vec<String> = report.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(chain, ["starting app", "loading config", "config.toml"]);
+}
+
+#[test]
+fn to_miette_does_not_consume_error() {
+    let err = anyerr!("still here").context("outer");
+    let report = to_miette(&err);
+
+    assert!(report.chain().count() > 1);
+    assert_eq!(err.to_string(), "outer");
+}
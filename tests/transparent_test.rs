@@ -0,0 +1,96 @@
+//! Tests for derive::Transparent and ResultExt::transparent()
+
+use okerr::derive::{Error, Transparent};
+use okerr::{Context, Result, ResultExt, anyerr};
+use std::io;
+
+#[derive(Error, Debug)]
+#[error("storage error")]
+struct StorageError(#[source] io::Error);
+
+#[test]
+fn transparent_display_delegates_to_source() {
+    let err = StorageError(io::Error::other("disk full"));
+    assert_eq!(err.transparent_display(), "disk full");
+}
+
+#[test]
+fn transparent_display_without_source() {
+    let err = io::Error::other("disk full");
+    assert_eq!(err.transparent_display(), "disk full");
+}
+
+#[test]
+fn empty_context_layer_is_removed() {
+    let result: Result<()> = Err(anyerr!("disk full")).context("writing").context("");
+    let err = result.transparent().unwrap_err();
+
+    assert_eq!(err.to_string(), "writing");
+    assert_eq!(format!("{err:#}"), "writing: disk full");
+}
+
+#[test]
+fn duplicated_context_layer_is_removed() {
+    let result: Result<()> = Err(anyerr!("disk full")).context("disk full");
+    let err = result.transparent().unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "disk full");
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn meaningful_top_message_is_preserved() {
+    let result: Result<()> = Err(io::Error::other("disk full").into());
+    let err = result.context("saving report").transparent().unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "saving report: disk full");
+    assert!(err.downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn single_level_is_unchanged() {
+    let result: Result<()> = Err(anyerr!(""));
+    let err = result.transparent().unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+}
+
+#[test]
+fn marked_error_stays_marked() {
+    let result: Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "config.toml").into());
+    let err = result
+        .context("config.toml")
+        .mark_user()
+        .transparent()
+        .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "config.toml");
+    assert_eq!(err.chain().count(), 1);
+    assert!(okerr::is_user_error(&err));
+    assert_eq!(
+        okerr::category::category(&err),
+        Some(okerr::category::Category::NotFound)
+    );
+}
+
+#[test]
+fn marker_layer_is_not_redundant() {
+    let result: Result<()> = Err(io::Error::other("disk full").into());
+    let err = result.mark_user().transparent().unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "disk full");
+    assert!(okerr::is_user_error(&err));
+}
+
+#[test]
+fn typed_causes_are_kept() {
+    let result: Result<()> = Err(StorageError(io::Error::other("disk full")).into());
+    let err = result
+        .context("storage error")
+        .transparent()
+        .mark_system()
+        .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "storage error: disk full");
+    assert!(err.chain().any(|cause| cause.is::<io::Error>()));
+    assert!(okerr::is_system_error(&err));
+}
@@ -0,0 +1,45 @@
+//! Tests for truncate_chain()
+
+use okerr::{anyerr, anyerr_chain, truncate_chain};
+
+fn chain_of(err: &okerr::Error) -> Vec<String> {
+    err.chain().map(|cause| cause.to_string()).collect()
+}
+
+fn six_levels() -> okerr::Error {
+    anyerr_chain((1..=6).rev().map(|level| format!("level {level}")))
+}
+
+#[test]
+fn truncate_chain_keeps_top_levels() {
+    let err = truncate_chain(six_levels(), 3);
+
+    assert_eq!(
+        chain_of(&err),
+        ["level 1", "level 2", "level 3", "... (3 more causes)"]
+    );
+}
+
+#[test]
+fn truncate_chain_singular_summary() {
+    let err = truncate_chain(six_levels(), 5);
+
+    assert_eq!(chain_of(&err).len(), 6);
+    assert_eq!(err.root_cause().to_string(), "... (1 more cause)");
+}
+
+#[test]
+fn truncate_chain_shallow_unchanged() {
+    let err = truncate_chain(six_levels(), 6);
+    assert_eq!(chain_of(&err).len(), 6);
+    assert_eq!(err.root_cause().to_string(), "level 6");
+
+    let err = truncate_chain(anyerr!("io").context("outer"), 10);
+    assert_eq!(chain_of(&err), ["outer", "io"]);
+}
+
+#[test]
+fn truncate_chain_zero() {
+    let err = truncate_chain(six_levels(), 0);
+    assert_eq!(chain_of(&err), ["... (6 more causes)"]);
+}
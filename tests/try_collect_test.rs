@@ -0,0 +1,74 @@
+//! Tests for TryCollect::try_collect()
+
+// `try_collect` is also the name of the unstable `Iterator::try_collect`.
+#![allow(unstable_name_collisions)]
+
+use okerr::{Result, TryCollect, anyerr};
+use std::collections::BTreeSet;
+
+fn parse(s: &str) -> Result<i32> {
+    Ok(s.parse()?)
+}
+
+#[test]
+fn try_collect_into_vec() {
+    let values: Vec<i32> = ["1", "2", "3"]
+        .into_iter()
+        .map(parse)
+        .try_collect()
+        .unwrap();
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn try_collect_into_other_container() {
+    let values: BTreeSet<i32> = ["3", "1", "3"]
+        .into_iter()
+        .map(parse)
+        .try_collect()
+        .unwrap();
+    assert_eq!(values.into_iter().collect::<Vec<_>>(), [1, 3]);
+}
+
+#[test]
+fn try_collect_failure_names_index() {
+    let result: Result<Vec<i32>> = ["1", "2", "three", "4"]
+        .into_iter()
+        .map(parse)
+        .try_collect();
+
+    let err = result.unwrap_err();
+    assert_eq!(err.to_string(), "collection failed at element 2");
+    assert_eq!(
+        err.root_cause().to_string(),
+        "invalid digit found in string"
+    );
+}
+
+#[test]
+fn try_collect_short_circuits() {
+    let mut visited = Vec::new();
+
+    let result: Result<Vec<i32>> = (0..10)
+        .map(|i| {
+            visited.push(i);
+            if i == 3 {
+                Err(anyerr!("fail at {i}"))
+            } else {
+                Ok(i)
+            }
+        })
+        .try_collect();
+
+    assert_eq!(
+        format!("{:#}", result.unwrap_err()),
+        "collection failed at element 3: fail at 3"
+    );
+    assert_eq!(visited, [0, 1, 2, 3]);
+}
+
+#[test]
+fn try_collect_empty() {
+    let values: Vec<i32> = std::iter::empty::<Result<i32>>().try_collect().unwrap();
+    assert!(values.is_empty());
+}
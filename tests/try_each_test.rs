@@ -0,0 +1,46 @@
+//! Tests for try_each()
+
+use okerr::{Result, anyerr, ensure, try_each};
+
+fn even(n: &u32) -> Result<()> {
+    ensure!(n.is_multiple_of(2), "{n} is odd");
+    Ok(())
+}
+
+#[test]
+fn try_each_all_pass() {
+    assert!(try_each([2, 4, 6], even).is_ok());
+}
+
+#[test]
+fn try_each_empty() {
+    assert!(try_each(Vec::<u32>::new(), |_| Err(anyerr!("never"))).is_ok());
+}
+
+#[test]
+fn try_each_stops_at_failing_index() {
+    let mut visited = Vec::new();
+
+    let err = try_each([2, 4, 5, 7, 8], |n| {
+        visited.push(*n);
+        even(n)
+    })
+    .unwrap_err();
+
+    assert_eq!(visited, [2, 4, 5]);
+    assert_eq!(err.to_string(), "failed on item #2");
+    assert_eq!(err.root_cause().to_string(), "5 is odd");
+}
+
+#[test]
+fn try_each_over_references() {
+    let names = vec!["alice".to_string(), String::new()];
+
+    let err = try_each(&names, |name| {
+        ensure!(!name.is_empty(), "empty name");
+        Ok(())
+    })
+    .unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "failed on item #1: empty name");
+}
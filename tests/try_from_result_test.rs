@@ -0,0 +1,58 @@
+//! Tests for convert::TryFromResult
+
+use okerr::convert::TryFromResult;
+use okerr::{Context, Result, ensure};
+
+#[derive(Debug, PartialEq)]
+struct Email(String);
+
+impl TryFromResult<String> for Email {
+    fn try_from_value(value: String) -> Result<Self> {
+        ensure!(value.contains('@'), "missing @ in {value:?}");
+        Ok(Email(value))
+    }
+}
+
+fn read_field(raw: Option<&str>) -> Result<String> {
+    raw.map(str::to_string).context("field is missing")
+}
+
+#[test]
+fn valid_input_builds_value() {
+    let email = Email::try_from_result(read_field(Some("a@b.c"))).unwrap();
+    assert_eq!(email, Email("a@b.c".to_string()));
+}
+
+#[test]
+fn invalid_value_names_target_type() {
+    let err = Email::try_from_result(read_field(Some("nope"))).unwrap_err();
+
+    assert_eq!(
+        format!("{err:#}"),
+        "constructing Email: missing @ in \"nope\""
+    );
+}
+
+#[test]
+fn failed_input_names_target_type() {
+    let err = Email::try_from_result(read_field(None)).unwrap_err();
+
+    assert_eq!(format!("{err:#}"), "constructing Email: field is missing");
+}
+
+#[test]
+fn generic_type_keeps_full_name() {
+    #[derive(Debug)]
+    struct Wrapper<T>(T);
+
+    impl TryFromResult<u8> for Wrapper<u8> {
+        fn try_from_value(value: u8) -> Result<Self> {
+            ensure!(value > 0, "zero");
+            Ok(Wrapper(value))
+        }
+    }
+
+    let err = Wrapper::<u8>::try_from_result(Ok(0)).unwrap_err();
+    assert!(err.to_string().starts_with("constructing "));
+    assert!(err.to_string().ends_with("Wrapper<u8>"));
+}
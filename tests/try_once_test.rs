@@ -0,0 +1,69 @@
+//! Tests for once::TryOnce
+
+use okerr::{anyerr, err, once::TryOnce};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[test]
+fn try_once_failed_init_can_be_retried() {
+    let once: TryOnce<i32> = TryOnce::new();
+
+    let result = once.get_or_try_init(|| err!("transient failure"));
+    assert_eq!(result.unwrap_err().to_string(), "transient failure");
+    assert!(once.get().is_none());
+
+    let result = once.get_or_try_init(|| Ok(42));
+    assert_eq!(*result.unwrap(), 42);
+    assert_eq!(once.get(), Some(&42));
+}
+
+#[test]
+fn try_once_success_is_cached() {
+    let once: TryOnce<String> = TryOnce::new();
+    let calls = AtomicUsize::new(0);
+
+    for _ in 0..3 {
+        let value = once
+            .get_or_try_init(|| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok("value".to_string())
+            })
+            .unwrap();
+
+        assert_eq!(value, "value");
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Not called anymore, even if it would fail
+    let value = once.get_or_try_init(|| Err(anyerr!("never called")));
+    assert_eq!(value.unwrap(), "value");
+}
+
+#[test]
+fn try_once_static() {
+    static ONCE: TryOnce<u64> = TryOnce::new();
+
+    assert!(ONCE.get_or_try_init(|| err!("fail")).is_err());
+    assert_eq!(*ONCE.get_or_try_init(|| Ok(7)).unwrap(), 7);
+}
+
+#[test]
+fn try_once_concurrent_init_runs_once() {
+    let once: TryOnce<usize> = TryOnce::new();
+    let calls = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                let value = once
+                    .get_or_try_init(|| Ok(calls.fetch_add(1, Ordering::SeqCst) + 100))
+                    .unwrap();
+
+                assert_eq!(*value, 100);
+            });
+        }
+    });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,36 @@
+//! Tests for unwrap_either() and ResultExt::map_err_into_ok()
+
+use okerr::{Result, ResultExt, anyerr, unwrap_either};
+
+#[test]
+fn unwrap_either_both_variants() {
+    assert_eq!(unwrap_either(Ok::<u8, u8>(1)), 1);
+    assert_eq!(unwrap_either(Err::<u8, u8>(2)), 2);
+}
+
+#[test]
+fn map_err_into_ok_keeps_ok_value() {
+    let result: Result<String> = Ok("fresh".to_string());
+    assert_eq!(
+        result.map_err_into_ok(|_| panic!("must not be called")),
+        "fresh"
+    );
+}
+
+#[test]
+fn map_err_into_ok_uses_fallback() {
+    let result: Result<String> = Err(anyerr!("cache miss"));
+    let value = result.map_err_into_ok(|err| format!("default ({err})"));
+
+    assert_eq!(value, "default (cache miss)");
+}
+
+#[test]
+fn map_err_into_ok_collapses_into_status() {
+    fn status(result: Result<()>) -> u16 {
+        result.map(|()| 200).map_err_into_ok(|_| 500)
+    }
+
+    assert_eq!(status(Ok(())), 200);
+    assert_eq!(status(Err(anyerr!("boom"))), 500);
+}
@@ -0,0 +1,50 @@
+//! Tests for the collection validators
+
+use okerr::validate::{ensure_no_duplicates, ensure_sorted, ensure_unique};
+
+#[test]
+fn ensure_sorted_accepts_sorted() {
+    assert!(ensure_sorted(&[1, 2, 2, 3], "ids").is_ok());
+    assert!(ensure_sorted::<i32>(&[], "ids").is_ok());
+    assert!(ensure_sorted(&["a"], "names").is_ok());
+}
+
+#[test]
+fn ensure_sorted_names_first_unsorted_pair() {
+    let err = ensure_sorted(&[1, 4, 2, 0], "ids").unwrap_err();
+    assert_eq!(err.to_string(), "ids must be sorted (4 comes before 2)");
+}
+
+#[test]
+fn ensure_unique_accepts_unique() {
+    assert!(ensure_unique(&[1, 2, 3], "ports").is_ok());
+    assert!(ensure_unique::<u16>(&[], "ports").is_ok());
+}
+
+#[test]
+fn ensure_unique_names_duplicate() {
+    let err = ensure_unique(&[80, 443, 8080, 443, 80], "ports").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "ports must contain unique values (443 is duplicated)"
+    );
+}
+
+#[test]
+fn ensure_no_duplicates_accepts_unique() {
+    assert!(ensure_no_duplicates(&["x", "y"], "keys").is_ok());
+}
+
+#[test]
+fn ensure_no_duplicates_names_duplicate() {
+    // Ord only, no Hash
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Version(u32, u32);
+
+    let err = ensure_no_duplicates(&[Version(1, 0), Version(1, 2), Version(1, 0)], "versions")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "versions must not contain duplicates (Version(1, 0) is duplicated)"
+    );
+}
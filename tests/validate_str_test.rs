@@ -0,0 +1,51 @@
+//! Tests for validate string validators
+
+use okerr::validate::{ensure_len_between, ensure_nonempty_str};
+
+#[test]
+fn ensure_nonempty_str_passes() {
+    assert!(ensure_nonempty_str("Alice", "username").is_ok());
+    assert!(ensure_nonempty_str(" ", "username").is_ok());
+}
+
+#[test]
+fn ensure_nonempty_str_fails() {
+    let err = ensure_nonempty_str("", "username").unwrap_err();
+    assert_eq!(err.to_string(), "username must not be empty");
+}
+
+#[test]
+fn ensure_len_between_passes() {
+    assert!(ensure_len_between("abc", 3, 5, "password").is_ok());
+    assert!(ensure_len_between("abcde", 3, 5, "password").is_ok());
+    // Characters, not bytes
+    assert!(ensure_len_between("été", 3, 3, "word").is_ok());
+}
+
+#[test]
+fn ensure_len_between_too_short() {
+    let err = ensure_len_between("ab", 3, 5, "password").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "password must be between 3 and 5 characters long (got 2)"
+    );
+}
+
+#[test]
+fn ensure_len_between_too_long() {
+    let err = ensure_len_between("abcdef", 3, 5, "password").unwrap_err();
+    assert!(err.to_string().starts_with("password must be between"));
+    assert!(err.to_string().ends_with("(got 6)"));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn ensure_matches_regex_passes_and_fails() {
+    use okerr::validate::ensure_matches_regex;
+
+    let re = regex::Regex::new(r"^\d{5}$").unwrap();
+    assert!(ensure_matches_regex("75001", &re, "zip code").is_ok());
+
+    let err = ensure_matches_regex("750", &re, "zip code").unwrap_err();
+    assert_eq!(err.to_string(), r"zip code must match the pattern ^\d{5}$");
+}
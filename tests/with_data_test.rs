@@ -0,0 +1,63 @@
+//! Tests for ResultExt::with_data() and get_data()
+
+use okerr::{Context, Result, ResultExt, anyerr, get_data};
+
+#[derive(Debug, Clone, PartialEq)]
+struct CreateUser {
+    name: String,
+    age: u8,
+}
+
+fn create(request: CreateUser) -> Result<u32> {
+    let result: Result<u32> = Err(anyerr!("name already taken"));
+    result.with_data(request).context("creating user")
+}
+
+#[test]
+fn get_data_returns_payload() {
+    let request = CreateUser {
+        name: "alice".to_string(),
+        age: 30,
+    };
+
+    let err = create(request.clone()).unwrap_err();
+
+    assert_eq!(get_data::<CreateUser>(&err), Some(&request));
+}
+
+#[test]
+fn with_data_keeps_chain() {
+    let err = create(CreateUser {
+        name: "bob".to_string(),
+        age: 20,
+    })
+    .context("handling request")
+    .unwrap_err();
+
+    let chain: Vec<String> = err.chain().map(|cause| cause.to_string()).collect();
+    assert_eq!(
+        chain,
+        ["handling request", "creating user", "name already taken"]
+    );
+}
+
+#[test]
+fn with_data_multiple_types() {
+    let result: Result<()> = Err(anyerr!("fail"));
+    let err = result
+        .with_data(42_u64)
+        .context("step")
+        .with_data("label")
+        .with_data(7_u64)
+        .unwrap_err();
+
+    // The last payload of a type wins
+    assert_eq!(get_data::<u64>(&err), Some(&7));
+    assert_eq!(get_data::<&str>(&err), Some(&"label"));
+    assert_eq!(get_data::<CreateUser>(&err), None);
+}
+
+#[test]
+fn get_data_none_without_payload() {
+    assert_eq!(get_data::<u64>(&anyerr!("plain")), None);
+}
@@ -0,0 +1,53 @@
+//! Tests for timing::with_deadline()
+
+use okerr::{Result, anyerr, timing::with_deadline};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn slow<T>(result: Result<T>) -> Result<T> {
+    thread::sleep(Duration::from_millis(30));
+    result
+}
+
+#[test]
+fn with_deadline_fast_ok() {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    assert_eq!(with_deadline(deadline, || Ok("fast")).unwrap(), "fast");
+}
+
+#[test]
+fn with_deadline_fast_err_unchanged() {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let result: Result<()> = with_deadline(deadline, || Err(anyerr!("failed")));
+
+    let err = result.unwrap_err();
+    assert_eq!(err.chain().count(), 1);
+    assert_eq!(err.to_string(), "failed");
+}
+
+#[test]
+fn with_deadline_slow_ok_becomes_error() {
+    let deadline = Instant::now() + Duration::from_millis(5);
+    let result = with_deadline(deadline, || slow(Ok(1)));
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().starts_with("exceeded deadline by "));
+}
+
+#[test]
+fn with_deadline_slow_err_gets_context() {
+    let deadline = Instant::now() + Duration::from_millis(5);
+    let result: Result<()> = with_deadline(deadline, || slow(Err(anyerr!("query failed"))));
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().starts_with("exceeded deadline by "));
+    assert_eq!(err.root_cause().to_string(), "query failed");
+}
+
+#[test]
+fn with_deadline_already_passed() {
+    let deadline = Instant::now();
+    thread::sleep(Duration::from_millis(1));
+
+    assert!(with_deadline(deadline, || Ok(())).is_err());
+}
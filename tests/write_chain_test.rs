@@ -0,0 +1,107 @@
+//! Tests for write_chain() and format_chain()
+
+use okerr::{Context, FormatOptions, Result, anyerr, format_chain, write_chain};
+use std::fmt;
+
+/// Writer backed by a fixed-size buffer, fails when full.
+struct FixedBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl<const N: usize> fmt::Write for FixedBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+
+        if end > N {
+            return Err(fmt::Error);
+        }
+
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn three_levels() -> okerr::Error {
+    let result: Result<()> = Err(anyerr!("root cause"))
+        .context("middle layer")
+        .context("outer layer");
+
+    result.unwrap_err()
+}
+
+#[test]
+fn write_chain_into_string() {
+    let err = three_levels();
+    let mut out = String::new();
+
+    write_chain(&mut out, &err, &FormatOptions::default()).unwrap();
+
+    assert_eq!(out, "outer layer\n  middle layer\n    root cause");
+}
+
+#[test]
+fn write_chain_respects_indent() {
+    let err = three_levels();
+    let mut out = String::new();
+
+    write_chain(&mut out, &err, &FormatOptions { indent: 3 }).unwrap();
+    assert_eq!(out, "outer layer\n   middle layer\n      root cause");
+
+    out.clear();
+    write_chain(&mut out, &err, &FormatOptions { indent: 0 }).unwrap();
+    assert_eq!(out, "outer layer\nmiddle layer\nroot cause");
+}
+
+#[test]
+fn write_chain_into_fixed_buffer() {
+    let err = three_levels();
+    let mut buf = FixedBuf::<128>::new();
+
+    write_chain(&mut buf, &err, &FormatOptions { indent: 1 }).unwrap();
+
+    assert_eq!(buf.as_str(), "outer layer\n middle layer\n  root cause");
+}
+
+#[test]
+fn write_chain_propagates_writer_error() {
+    let err = three_levels();
+    let mut buf = FixedBuf::<8>::new();
+
+    assert!(write_chain(&mut buf, &err, &FormatOptions::default()).is_err());
+}
+
+#[test]
+fn write_chain_single_error() {
+    let err = anyerr!("alone");
+    let mut out = String::new();
+
+    write_chain(&mut out, &err, &FormatOptions::default()).unwrap();
+
+    assert_eq!(out, "alone");
+}
+
+#[test]
+fn format_chain_matches_write_chain() {
+    let err = three_levels();
+    let opts = FormatOptions { indent: 4 };
+    let mut out = String::new();
+
+    write_chain(&mut out, &err, &opts).unwrap();
+
+    assert_eq!(format_chain(&err, &opts), out);
+}
@@ -0,0 +1,37 @@
+//! Tests for zip3()
+
+use okerr::{Context, MultiError, Result, anyerr, zip3};
+use std::io;
+
+#[test]
+fn zip3_all_ok() {
+    let a: Result<u8> = Ok(1);
+    let b: Result<&str> = Ok("two");
+    let c: Result<bool> = Ok(true);
+    assert_eq!(zip3(a, b, c).unwrap(), (1, "two", true));
+}
+
+#[test]
+fn zip3_single_failure_unchanged() {
+    let a: Result<u8> = Ok(1);
+    let b: Result<u8> = Err(io::Error::new(io::ErrorKind::NotFound, "no file").into());
+    let c: Result<u8> = Ok(3);
+
+    let err = zip3(a, b, c).unwrap_err();
+    assert_eq!(err.to_string(), "no file");
+    assert!(err.downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn zip3_multiple_failures_listed() {
+    let a: Result<u8> = Err(anyerr!("missing host"));
+    let b: Result<u8> = Err(anyerr!("invalid port")).context("parsing port");
+    let c: Result<u8> = Err(anyerr!("missing user"));
+
+    let err = zip3(a, b, c).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "3 errors occurred:\n  - missing host\n  - parsing port: invalid port\n  - missing user"
+    );
+    assert_eq!(err.downcast_ref::<MultiError>().unwrap().len(), 3);
+}
@@ -0,0 +1,43 @@
+//! Tests for zip()
+
+use okerr::{Context, MultiError, Result, anyerr, zip};
+use std::io;
+
+#[test]
+fn zip_both_ok() {
+    let a: Result<u8> = Ok(1);
+    let b: Result<&str> = Ok("two");
+    assert_eq!(zip(a, b).unwrap(), (1, "two"));
+}
+
+#[test]
+fn zip_first_fails() {
+    let a: Result<u8> = Err(io::Error::new(io::ErrorKind::NotFound, "no file").into());
+    let b: Result<u8> = Ok(2);
+
+    let err = zip(a, b).unwrap_err();
+    assert_eq!(err.to_string(), "no file");
+    assert!(err.downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn zip_second_fails() {
+    let a: Result<u8> = Ok(1);
+    let b: Result<u8> = Err(anyerr!("invalid port")).context("parsing port");
+
+    let err = zip(a, b).unwrap_err();
+    assert_eq!(format!("{err:#}"), "parsing port: invalid port");
+}
+
+#[test]
+fn zip_both_fail_lists_both() {
+    let a: Result<u8> = Err(anyerr!("missing host"));
+    let b: Result<u8> = Err(anyerr!("invalid port")).context("parsing port");
+
+    let err = zip(a, b).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2 errors occurred:\n  - missing host\n  - parsing port: invalid port"
+    );
+    assert_eq!(err.downcast_ref::<MultiError>().unwrap().len(), 2);
+}